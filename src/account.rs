@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Global, Task};
+use http_client::{AsyncBody, HttpClient, Request};
+use scraper::{Html, Selector};
+use smol::io::AsyncReadExt as _;
+
+use crate::cookie::CookieJar;
+use crate::mirror;
+use crate::page::{self, encode};
+
+const COOKIE_TIME: u32 = 2592000;
+
+pub fn init(cx: &mut App) {
+    let account = cx.new(Account::new);
+    cx.set_global(GlobalAccount(account));
+}
+
+pub struct Account {
+    cookie_jar: Arc<CookieJar>,
+    is_logging_in: bool,
+    _expired_task: Task<()>,
+}
+
+impl Account {
+    fn new(cx: &mut Context<Self>) -> Self {
+        let cookie_jar = CookieJar::global(cx);
+        let expired = cookie_jar.expired();
+        let expired_task = cx.spawn(async move |this, cx| {
+            while expired.recv().await.is_ok() {
+                let Some(this) = this.upgrade() else {
+                    break;
+                };
+                this.update(cx, |_, cx| {
+                    cx.emit(AccountEvent::Expired);
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
+
+        Self {
+            cookie_jar,
+            is_logging_in: false,
+            _expired_task: expired_task,
+        }
+    }
+
+    pub fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalAccount>().0.clone()
+    }
+
+    pub fn username(&self) -> Option<String> {
+        self.cookie_jar.username()
+    }
+
+    pub fn is_logging_in(&self) -> bool {
+        self.is_logging_in
+    }
+
+    pub fn login(
+        &mut self,
+        username: String,
+        password: String,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        self.is_logging_in = true;
+        cx.notify();
+
+        let client = cx.http_client();
+        let cookie_jar = self.cookie_jar.clone();
+        cx.spawn(async move |this, cx| {
+            let result = match Self::submit(client, &username, &password).await {
+                Ok(()) => cookie_jar.login(username),
+                Err(error) => Err(error),
+            };
+            this.update(cx, |this, cx| {
+                this.is_logging_in = false;
+                cx.notify();
+            })
+            .ok();
+
+            result
+        })
+    }
+
+    pub fn logout(&mut self, cx: &mut Context<Self>) -> anyhow::Result<()> {
+        self.cookie_jar.logout()?;
+        cx.notify();
+
+        Ok(())
+    }
+
+    async fn submit(
+        http_client: Arc<dyn HttpClient>,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !username.is_empty() && !password.is_empty(),
+            "请输入用户名和密码"
+        );
+
+        let (text, _) = page::fetch(&http_client, |cookie| {
+            Request::builder()
+                .method("GET")
+                .uri(mirror::forum_url("member.php?mod=logging&action=login"))
+                .header("Cookie", cookie)
+                .header("Cache-Control", "no-cache")
+                .header("Accept-Language", "zh-CN,zh-Hans;q=0.9")
+                .body(AsyncBody::empty())
+                .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))
+        })
+        .await?;
+        let formhash = page::formhash(&text).ok_or_else(|| anyhow::anyhow!("解析登录表单失败"))?;
+        let loginhash = Self::parse_loginhash(&text).unwrap_or_default();
+
+        let body = format!(
+            "formhash={}&referer={}&username={}&password={}&questionid=0&answer=&cookietime={COOKIE_TIME}",
+            encode(&formhash),
+            encode(&mirror::forum_url("forum.php")),
+            encode(username),
+            encode(password),
+        );
+        let request = Request::builder()
+            .method("POST")
+            .uri(mirror::forum_url(&format!(
+                "member.php?mod=logging&action=login&loginsubmit=yes&loginhash={loginhash}&inajax=1"
+            )))
+            .header("Cookie", "existmag=mag")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .header("Accept-Language", "zh-CN,zh-Hans;q=0.9")
+            .body(AsyncBody::from(body))
+            .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))?;
+        let response = http_client.send(request).await?;
+        anyhow::ensure!(
+            response.status().is_success(),
+            "登录失败 - {}",
+            response.status()
+        );
+
+        let mut text = String::new();
+        let mut body = response.into_body();
+        body.read_to_string(&mut text)
+            .await
+            .map_err(|error| anyhow::anyhow!("读取内容失败 - {error}"))?;
+        if text.contains("succeedhandle_") {
+            return Ok(());
+        }
+
+        match page::ajax_error(&text) {
+            Some(message) => anyhow::bail!("登录失败 - {message}"),
+            None => anyhow::bail!("登录失败"),
+        }
+    }
+
+    fn parse_loginhash(text: &str) -> Option<String> {
+        let selector = Selector::parse("form[name=\"login\"]")
+            .expect("Failed to parse selector: form[name=\"login\"]");
+
+        Html::parse_document(text)
+            .select(&selector)
+            .next()
+            .and_then(|form| form.attr("action"))
+            .and_then(|action| {
+                action
+                    .split(&['?', '&'])
+                    .find_map(|pair| pair.strip_prefix("loginhash="))
+                    .map(String::from)
+            })
+    }
+}
+
+pub enum AccountEvent {
+    Expired,
+}
+
+impl EventEmitter<AccountEvent> for Account {}
+
+struct GlobalAccount(Entity<Account>);
+
+impl Global for GlobalAccount {}
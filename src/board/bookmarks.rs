@@ -0,0 +1,160 @@
+use gpui::{
+    App, AppContext as _, Context, Entity, FocusHandle, InteractiveElement, IntoElement,
+    MouseButton, ParentElement as _, Pixels, Render, SharedString, StatefulInteractiveElement as _,
+    Styled as _, Window, div, px,
+};
+use gpui_component::button::{Button, ButtonVariants as _};
+use gpui_component::label::Label;
+use gpui_component::{ActiveTheme as _, ContextModal as _, Sizable as _, StyledExt as _};
+
+use super::ViewState;
+use super::detail::DetailEvent;
+use super::tabs::DetailTabs;
+use crate::bookmark::{Bookmark, BookmarkStore};
+use crate::icon::IconName;
+
+const BOOKMARKS_WIDTH: Pixels = px(700.);
+
+pub struct Bookmarks {
+    bookmark_store: Entity<BookmarkStore>,
+    tabs: Entity<DetailTabs>,
+    focus_handle: FocusHandle,
+}
+
+impl Bookmarks {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let bookmark_store = BookmarkStore::global(cx);
+        cx.observe(&bookmark_store, |_, _, cx| cx.notify()).detach();
+        let tabs = cx.new(|cx| DetailTabs::new(window, cx));
+
+        Self {
+            bookmark_store,
+            tabs,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn tabs(&self) -> &Entity<DetailTabs> {
+        &self.tabs
+    }
+
+    pub fn view_state(&self, cx: &App) -> ViewState {
+        ViewState::capture(None, &self.tabs, cx)
+    }
+
+    pub fn restore(&mut self, state: ViewState, window: &mut Window, cx: &mut Context<Self>) {
+        state.restore(None, &self.tabs, window, cx);
+    }
+
+    fn on_item_click(&mut self, href: SharedString, fid: Option<u32>, cx: &mut Context<Self>) {
+        self.tabs.update(cx, |tabs, cx| {
+            tabs.send(DetailEvent::Load(href, fid, 1), cx)
+        });
+    }
+
+    fn on_remove(&mut self, href: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let result = self
+            .bookmark_store
+            .update(cx, |bookmark_store, cx| bookmark_store.remove(&href, cx));
+        if let Err(error) = result {
+            window.push_notification(error.to_string(), cx);
+        }
+    }
+
+    fn render_bookmark(&self, bookmark: &Bookmark, cx: &mut Context<Self>) -> impl IntoElement {
+        let href = bookmark.href.clone();
+        let fid = bookmark.fid;
+        let remove_href = bookmark.href.clone();
+        let theme = cx.theme();
+
+        div()
+            .mt_2()
+            .mx_2()
+            .p_2()
+            .rounded_md()
+            .bg(theme.secondary_hover)
+            .border_1()
+            .border_color(theme.border)
+            .hover(|style| style.bg(theme.secondary_active))
+            .cursor_pointer()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _, cx| this.on_item_click(href.clone(), fid, cx)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_start()
+                    .justify_between()
+                    .gap_1()
+                    .child(Label::new(bookmark.title.clone()).font_semibold().text_lg())
+                    .child(
+                        div()
+                            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                            .child(
+                                Button::new(bookmark.href.clone())
+                                    .icon(IconName::Trash)
+                                    .ghost()
+                                    .small()
+                                    .cursor_pointer()
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.on_remove(remove_href.clone(), window, cx)
+                                    })),
+                            ),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .pt_1()
+                    .gap_1()
+                    .items_center()
+                    .child(
+                        Label::new(bookmark.author.clone())
+                            .text_color(theme.blue)
+                            .font_light()
+                            .text_sm(),
+                    )
+                    .child(
+                        Label::new(
+                            bookmark
+                                .saved_at
+                                .format("收藏于 %Y-%m-%d %H:%M")
+                                .to_string(),
+                        )
+                        .text_color(theme.yellow)
+                        .font_light()
+                        .text_sm(),
+                    ),
+            )
+    }
+}
+
+impl Render for Bookmarks {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let bookmarks = self.bookmark_store.read(cx).bookmarks().to_vec();
+        let bookmarks = bookmarks
+            .iter()
+            .map(|bookmark| self.render_bookmark(bookmark, cx).into_any_element())
+            .collect::<Vec<_>>();
+        let theme = cx.theme();
+
+        div()
+            .track_focus(&self.focus_handle)
+            .w_full()
+            .h_full()
+            .flex()
+            .child(
+                div()
+                    .id("Bookmarks")
+                    .h_full()
+                    .w(BOOKMARKS_WIDTH)
+                    .pb_2()
+                    .border_r_1()
+                    .border_color(theme.border)
+                    .overflow_y_scroll()
+                    .children(bookmarks),
+            )
+            .child(self.tabs.clone())
+    }
+}
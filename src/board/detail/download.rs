@@ -0,0 +1,119 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use gpui::SharedString;
+use http_client::{AsyncBody, HttpClient, Request};
+use smol::io::AsyncReadExt as _;
+
+use super::talk::{Content, TalkPage};
+use crate::link::ThreadLink;
+use crate::store;
+
+pub struct ThreadImage {
+    pub floor: u32,
+    pub order: usize,
+    pub src: SharedString,
+}
+
+impl ThreadImage {
+    pub fn file_name(&self) -> String {
+        let extension = url_file_name(&self.src)
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase())
+            .filter(|extension| {
+                !extension.is_empty()
+                    && extension.len() <= 4
+                    && extension.chars().all(|c| c.is_ascii_alphanumeric())
+            })
+            .unwrap_or_else(|| "jpg".to_string());
+
+        format!("{}-{}.{extension}", self.floor, self.order)
+    }
+}
+
+pub fn thread_images(talk_page: &TalkPage) -> Vec<ThreadImage> {
+    talk_page
+        .talks
+        .iter()
+        .flat_map(|talk| {
+            talk.content
+                .iter()
+                .filter_map(|content| match content {
+                    Content::Image(src) => Some(src.clone()),
+                    _ => None,
+                })
+                .enumerate()
+                .map(|(idx, src)| ThreadImage {
+                    floor: talk.count,
+                    order: idx + 1,
+                    src,
+                })
+        })
+        .collect()
+}
+
+pub fn download_dir() -> PathBuf {
+    dirs::download_dir()
+        .map(|dir| dir.join("bustop"))
+        .unwrap_or_else(|| store::data_dir().join("download"))
+}
+
+pub fn thread_dir(talk_page: &TalkPage) -> PathBuf {
+    let name = talk_page
+        .title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(60)
+        .collect::<String>();
+    let name = name.trim().trim_matches('.');
+    let name = if name.is_empty() { "thread" } else { name };
+    let name = match ThreadLink::parse(&talk_page.href) {
+        Some(link) => format!("{name}-{}", link.tid),
+        None => name.to_string(),
+    };
+
+    download_dir().join(name)
+}
+
+pub fn image_name(src: &str) -> String {
+    url_file_name(src)
+        .filter(|name| !name.is_empty())
+        .unwrap_or("image.jpg")
+        .to_string()
+}
+
+pub async fn save_image(
+    http_client: Arc<dyn HttpClient>,
+    src: &str,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let request = Request::builder()
+        .method("GET")
+        .uri(src)
+        .header("Accept-Language", "zh-CN,zh-Hans;q=0.9")
+        .body(AsyncBody::empty())
+        .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))?;
+    let response = http_client.send(request).await?;
+    anyhow::ensure!(response.status().is_success(), "加载图片失败 - {src}");
+
+    let mut bytes = Vec::new();
+    let mut body = response.into_body();
+    body.read_to_end(&mut bytes)
+        .await
+        .map_err(|error| anyhow::anyhow!("读取内容失败 - {error}"))?;
+    smol::fs::write(path, bytes)
+        .await
+        .map_err(|error| anyhow::anyhow!("写入文件失败 - {error}"))?;
+
+    Ok(())
+}
+
+fn url_file_name(src: &str) -> Option<&str> {
+    let path = src.split(['?', '#']).next().unwrap_or(src);
+
+    path.rsplit_once('/').map(|(_, name)| name)
+}
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use http_client::HttpClient;
+use serde::Serialize;
+
+use super::download;
+use super::talk::{Content, Reply, Talk, TalkPage};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn all() -> [Self; 3] {
+        [Self::Markdown, Self::Html, Self::Json]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Markdown => "Markdown",
+            Self::Html => "HTML",
+            Self::Json => "JSON",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Json => "json",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExportDocument<'a> {
+    #[serde(flatten)]
+    thread: &'a TalkPage,
+    images: &'a BTreeMap<String, String>,
+}
+
+pub async fn export(
+    http_client: Arc<dyn HttpClient>,
+    talk_page: &TalkPage,
+    format: ExportFormat,
+    download_images: bool,
+) -> anyhow::Result<PathBuf> {
+    let dir = download::thread_dir(talk_page);
+    smol::fs::create_dir_all(&dir)
+        .await
+        .map_err(|error| anyhow::anyhow!("创建目录失败 - {error}"))?;
+
+    let images = if download_images {
+        save_images(http_client, talk_page, &dir).await?
+    } else {
+        BTreeMap::new()
+    };
+    let text = match format {
+        ExportFormat::Markdown => to_markdown(talk_page, &images),
+        ExportFormat::Html => to_html(talk_page, &images),
+        ExportFormat::Json => serde_json::to_string_pretty(&ExportDocument {
+            thread: talk_page,
+            images: &images,
+        })
+        .map_err(|error| anyhow::anyhow!("序列化失败 - {error}"))?,
+    };
+    let path = dir.join(format!("thread.{}", format.extension()));
+    smol::fs::write(&path, text)
+        .await
+        .map_err(|error| anyhow::anyhow!("写入文件失败 - {error}"))?;
+
+    Ok(path)
+}
+
+async fn save_images(
+    http_client: Arc<dyn HttpClient>,
+    talk_page: &TalkPage,
+    dir: &Path,
+) -> anyhow::Result<BTreeMap<String, String>> {
+    let image_dir = dir.join("images");
+    smol::fs::create_dir_all(&image_dir)
+        .await
+        .map_err(|error| anyhow::anyhow!("创建目录失败 - {error}"))?;
+
+    let mut images = BTreeMap::new();
+    for image in download::thread_images(talk_page) {
+        if images.contains_key(image.src.as_ref()) {
+            continue;
+        }
+
+        let name = image.file_name();
+        if download::save_image(http_client.clone(), &image.src, &image_dir.join(&name))
+            .await
+            .is_ok()
+        {
+            images.insert(image.src.to_string(), format!("images/{name}"));
+        }
+    }
+
+    Ok(images)
+}
+
+fn image_src<'a>(src: &'a str, images: &'a BTreeMap<String, String>) -> &'a str {
+    images.get(src).map(|path| path.as_str()).unwrap_or(src)
+}
+
+fn to_markdown(talk_page: &TalkPage, images: &BTreeMap<String, String>) -> String {
+    let mut text = format!(
+        "# {}\n\n作者: {}  \n原帖: <{}>\n",
+        talk_page.title, talk_page.author_name, talk_page.href
+    );
+    for talk in talk_page.talks.iter() {
+        text.push_str("\n---\n\n");
+        markdown_talk(&mut text, talk, images);
+    }
+
+    text
+}
+
+fn markdown_talk(text: &mut String, talk: &Talk, images: &BTreeMap<String, String>) {
+    text.push_str(&format!(
+        "## #{} {} @ {}\n",
+        talk.count,
+        talk.author_name,
+        talk.published_at.format("%Y-%m-%d %H:%M:%S")
+    ));
+    for content in talk.content.iter() {
+        text.push('\n');
+        match content {
+            Content::Text(content) => text.push_str(&format!("{content}\n")),
+            Content::Image(src) => text.push_str(&format!("![]({})\n", image_src(src, images))),
+            Content::Quote(name, date_time, content) => {
+                text.push_str(&format!(
+                    "> **{name}** @ {}\n",
+                    date_time.format("%Y-%m-%d %H:%M")
+                ));
+                for line in content.lines() {
+                    text.push_str(&format!("> {line}\n"));
+                }
+            }
+        }
+    }
+    if !talk.replys.is_empty() {
+        text.push_str("\n点评:\n\n");
+        for reply in talk.replys.iter() {
+            text.push_str(&format!(
+                "- **{}** @ {}: {}\n",
+                reply.author_name,
+                reply.published_at.format("%Y-%m-%d %H:%M"),
+                reply.content
+            ));
+        }
+    }
+}
+
+fn to_html(talk_page: &TalkPage, images: &BTreeMap<String, String>) -> String {
+    let mut text =
+        String::from("<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n");
+    text.push_str(&format!("<title>{}</title>\n", escape(&talk_page.title)));
+    text.push_str(
+        "<style>\n\
+         body { max-width: 800px; margin: 0 auto; padding: 16px; font-family: sans-serif; line-height: 1.6; }\n\
+         .talk { padding: 12px 0; border-top: 1px solid #ddd; }\n\
+         .meta { color: #888; font-size: 14px; }\n\
+         .quote { padding: 8px; margin: 8px 0; background: #f4f4f4; border-radius: 6px; }\n\
+         .replys { margin-top: 8px; padding-left: 16px; font-size: 14px; }\n\
+         img { max-width: 100%; border-radius: 6px; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    text.push_str(&format!(
+        "<h1>{}</h1>\n<p class=\"meta\">作者: {} · <a href=\"{}\">原帖</a></p>\n",
+        escape(&talk_page.title),
+        escape(&talk_page.author_name),
+        escape(&talk_page.href)
+    ));
+    for talk in talk_page.talks.iter() {
+        html_talk(&mut text, talk, images);
+    }
+    text.push_str("</body>\n</html>\n");
+
+    text
+}
+
+fn html_talk(text: &mut String, talk: &Talk, images: &BTreeMap<String, String>) {
+    text.push_str("<div class=\"talk\">\n");
+    text.push_str(&format!(
+        "<p class=\"meta\">#{} {} @ {}</p>\n",
+        talk.count,
+        escape(&talk.author_name),
+        talk.published_at.format("%Y-%m-%d %H:%M:%S")
+    ));
+    for content in talk.content.iter() {
+        match content {
+            Content::Text(content) => text.push_str(&format!("<p>{}</p>\n", escape(content))),
+            Content::Image(src) => text.push_str(&format!(
+                "<p><img src=\"{}\"></p>\n",
+                escape(image_src(src, images))
+            )),
+            Content::Quote(name, date_time, content) => text.push_str(&format!(
+                "<div class=\"quote\"><p class=\"meta\">{} @ {}</p><p>{}</p></div>\n",
+                escape(name),
+                date_time.format("%Y-%m-%d %H:%M"),
+                escape(content)
+            )),
+        }
+    }
+    if !talk.replys.is_empty() {
+        text.push_str("<ul class=\"replys\">\n");
+        for reply in talk.replys.iter() {
+            html_reply(text, reply);
+        }
+        text.push_str("</ul>\n");
+    }
+    text.push_str("</div>\n");
+}
+
+fn html_reply(text: &mut String, reply: &Reply) {
+    text.push_str(&format!(
+        "<li><b>{}</b> <span class=\"meta\">@ {}</span> {}</li>\n",
+        escape(&reply.author_name),
+        reply.published_at.format("%Y-%m-%d %H:%M"),
+        escape(&reply.content)
+    ));
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,1940 @@
+mod download;
+mod export;
+mod selector;
+mod talk;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use ego_tree::NodeRef;
+use export::ExportFormat;
+use gpui::{
+    AnyElement, AnyWindowHandle, App, AppContext, AsyncApp, Context, Entity, EntityId,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement, ListAlignment,
+    ListOffset, ListState, MouseButton, ParentElement as _, Pixels, Render, SharedString,
+    Styled as _, Task, WeakEntity, Window, div, img, list, prelude::FluentBuilder as _, px,
+};
+use gpui_component::avatar::Avatar;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::checkbox::Checkbox;
+use gpui_component::indicator::Indicator;
+use gpui_component::input::{
+    InputEvent, InputState, NumberInput, NumberInputEvent, StepAction, TextInput,
+};
+use gpui_component::label::Label;
+use gpui_component::{
+    ActiveTheme as _, ContextModal as _, Disableable, Selectable as _, Sizable as _, StyledExt as _,
+};
+use http_client::{AsyncBody, HttpClient, Request};
+use scraper::{ElementRef, Html, Node};
+use selector::{SelectorOverrides, Selectors};
+use talk::{Content, Reply, Talk, TalkPage};
+
+use super::info::FIND_FID;
+use super::prefetch;
+
+use crate::account::Account;
+use crate::bookmark::{Bookmark, BookmarkStore};
+use crate::icon::IconName;
+use crate::image_viewer::ImageViewer;
+use crate::page;
+use crate::reply;
+use crate::scroll;
+use crate::watch::{Watch, WatchStore};
+
+const PAGER_HEIGHT: Pixels = px(50.);
+const LOAD_MORE_THRESHOLD: usize = 3;
+
+pub struct Detail {
+    tab: EntityId,
+    selectors: Arc<Selectors>,
+    fid: Option<u32>,
+    list_state: ListState,
+    page: u32,
+    page_state: Entity<InputState>,
+    page_input_value: u32,
+    is_loading: bool,
+    load_generation: usize,
+    load_task: Option<Task<()>>,
+    is_appending: bool,
+    append_task: Option<Task<()>>,
+    prefetched: HashMap<u32, (Update, Option<NaiveDateTime>)>,
+    prefetch_tasks: HashMap<u32, Task<()>>,
+    load_error: Option<(SharedString, Option<u32>, u32, SharedString)>,
+    pending_floor: Option<u32>,
+    pending_scroll: Option<ListOffset>,
+    export_format: ExportFormat,
+    export_images: bool,
+    is_exporting: bool,
+    download_task: Option<Task<()>>,
+    download_progress: (usize, usize),
+    cached_at: Option<NaiveDateTime>,
+    reply_state: Entity<InputState>,
+    is_replying: bool,
+    talk: Option<TalkPage>,
+    focus_handle: FocusHandle,
+    window_handle: AnyWindowHandle,
+}
+
+impl Detail {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let entity = cx.entity();
+        cx.subscribe(&entity, Self::on_event).detach();
+        cx.observe(&BookmarkStore::global(cx), |_, _, cx| cx.notify())
+            .detach();
+        cx.observe(&WatchStore::global(cx), |_, _, cx| cx.notify())
+            .detach();
+        let account = Account::global(cx);
+        let mut username = account.read(cx).username();
+        cx.observe(&account, move |this, account, cx| {
+            let current = account.read(cx).username();
+            if current != username {
+                username = current;
+                this.clear_prefetch();
+            }
+            cx.notify();
+        })
+        .detach();
+        let reply_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line()
+                .placeholder("回复内容")
+        });
+        let page_state = cx.new(|cx| InputState::new(window, cx).placeholder(""));
+        cx.subscribe_in(&page_state, window, Self::on_input_event)
+            .detach();
+        cx.subscribe_in(&page_state, window, Self::on_number_input_event)
+            .detach();
+        let list_state = ListState::new(0, ListAlignment::Top, px(1000.));
+        let this = entity.downgrade();
+        list_state.set_scroll_handler(move |event, _, cx| {
+            if event.visible_range.end + LOAD_MORE_THRESHOLD < event.count {
+                return;
+            }
+
+            this.update(cx, |this, cx| this.load_more(cx)).ok();
+        });
+
+        Self {
+            tab: entity.entity_id(),
+            selectors: Arc::new(Selectors::new(SelectorOverrides::default())),
+            fid: None,
+            list_state,
+            page: 0,
+            page_state,
+            page_input_value: 0,
+            is_loading: false,
+            load_generation: 0,
+            load_task: None,
+            is_appending: false,
+            append_task: None,
+            prefetched: HashMap::new(),
+            prefetch_tasks: HashMap::new(),
+            load_error: None,
+            pending_floor: None,
+            pending_scroll: None,
+            export_format: ExportFormat::Markdown,
+            export_images: false,
+            is_exporting: false,
+            download_task: None,
+            download_progress: (0, 0),
+            cached_at: None,
+            reply_state,
+            is_replying: false,
+            talk: None,
+            focus_handle: cx.focus_handle(),
+            window_handle: window.window_handle(),
+        }
+    }
+
+    fn on_input_event(
+        &mut self,
+        this: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref talk) = self.talk else {
+            return;
+        };
+        match event {
+            InputEvent::PressEnter { secondary: _ } => {
+                let page = self.page_input_value;
+                cx.emit(DetailEvent::Load(talk.href.clone(), self.fid, page));
+                cx.focus_self(window);
+                cx.notify();
+            }
+            InputEvent::Change(text) => {
+                if this == &self.page_state {
+                    if let Ok(page) = text.parse::<u32>() {
+                        let max_page = self.talk.as_ref().map(|talk| talk.total_page).unwrap_or(1);
+                        if page != 0 && page <= max_page {
+                            self.page_input_value = page;
+                            return;
+                        }
+
+                        if page == 0 {
+                            self.page_input_value = 1;
+                        }
+
+                        if page > max_page {
+                            self.page_input_value = max_page;
+                        }
+                    }
+
+                    this.update(cx, |input, cx| {
+                        input.set_value(self.page_input_value.to_string(), window, cx);
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_number_input_event(
+        &mut self,
+        this: &Entity<InputState>,
+        event: &NumberInputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            NumberInputEvent::Step(step_action) => match step_action {
+                StepAction::Decrement => {
+                    if this == &self.page_state {
+                        if self.page_input_value <= 1 {
+                            return;
+                        }
+
+                        self.page_input_value = self.page_input_value - 1;
+                        this.update(cx, |input, cx| {
+                            input.set_value(self.page_input_value.to_string(), window, cx);
+                        });
+                    }
+                }
+                StepAction::Increment => {
+                    if this == &self.page_state {
+                        let max_page = self.talk.as_ref().map(|talk| talk.total_page).unwrap_or(1);
+                        if self.page_input_value == max_page {
+                            return;
+                        }
+
+                        self.page_input_value = self.page_input_value + 1;
+                        this.update(cx, |input, cx| {
+                            input.set_value(self.page_input_value.to_string(), window, cx);
+                        });
+                    }
+                }
+            },
+        }
+    }
+
+    fn on_event(&mut self, _: Entity<Self>, evt: &DetailEvent, cx: &mut Context<Self>) {
+        match evt {
+            DetailEvent::Load(detail_url, fid, page) => {
+                self.pending_floor = None;
+                self.pending_scroll = None;
+                self.emit_opened(detail_url, cx);
+                self.event_load(detail_url, *fid, *page, false, cx);
+            }
+            DetailEvent::Reload(detail_url, page) => {
+                self.pending_floor = None;
+                self.pending_scroll = None;
+                self.event_load(detail_url, self.fid, *page, true, cx);
+            }
+            DetailEvent::LoadFloor(detail_url, fid, page, floor) => {
+                self.pending_floor = Some(*floor);
+                self.pending_scroll = None;
+                self.emit_opened(detail_url, cx);
+                self.event_load(detail_url, *fid, *page, false, cx);
+            }
+            DetailEvent::Restore(location) => self.event_restore(location, cx),
+            DetailEvent::Close => self.event_close(cx),
+            _ => {}
+        }
+    }
+
+    pub fn title(&self) -> Option<SharedString> {
+        self.talk.as_ref().map(|talk| talk.title.clone())
+    }
+
+    pub fn location(&self) -> Option<ThreadLocation> {
+        self.talk.as_ref().map(|talk| ThreadLocation {
+            tab: self.tab,
+            href: talk.href.clone(),
+            fid: self.fid,
+            page: self.page,
+            scroll_top: self.list_state.logical_scroll_top(),
+        })
+    }
+
+    fn emit_opened(&self, url: &SharedString, cx: &mut Context<Self>) {
+        let previous = self.location();
+        let is_same = previous
+            .as_ref()
+            .map(|previous| &previous.href == url)
+            .unwrap_or(false);
+        if !is_same {
+            cx.emit(DetailEvent::Opened(previous));
+        }
+    }
+
+    fn event_restore(&mut self, location: &ThreadLocation, cx: &mut Context<Self>) {
+        let is_shown = !self.is_loading
+            && self.page == location.page
+            && self
+                .talk
+                .as_ref()
+                .map(|talk| talk.href == location.href)
+                .unwrap_or(false);
+        if is_shown {
+            self.list_state.scroll_to(location.scroll_top);
+            cx.notify();
+            return;
+        }
+
+        self.pending_floor = None;
+        self.pending_scroll = Some(location.scroll_top);
+        self.event_load(&location.href, location.fid, location.page, false, cx);
+    }
+
+    fn event_close(&mut self, cx: &mut Context<Self>) {
+        self.load_generation += 1;
+        self.load_task = None;
+        self.append_task = None;
+        self.clear_prefetch();
+        self.is_loading = false;
+        self.is_appending = false;
+        self.load_error = None;
+        self.pending_floor = None;
+        self.pending_scroll = None;
+        self.cached_at = None;
+        self.page = 0;
+        self.talk = None;
+        self.list_state.reset(0);
+        cx.notify();
+    }
+
+    fn event_load(
+        &mut self,
+        url: &SharedString,
+        fid: Option<u32>,
+        page: u32,
+        no_cache: bool,
+        cx: &mut Context<Self>,
+    ) {
+        self.load_generation += 1;
+        let generation = self.load_generation;
+        self.append_task = None;
+        self.is_appending = false;
+        let is_current = self
+            .talk
+            .as_ref()
+            .map(|talk| &talk.href == url)
+            .unwrap_or(false);
+        if !is_current || no_cache {
+            self.clear_prefetch();
+        } else if let Some((update, cached_at)) = self.prefetched.remove(&page) {
+            self.load_task = None;
+            self.load_error = None;
+            self.load_success(update, page, cached_at, cx);
+            return;
+        }
+        let pending = self.prefetch_tasks.remove(&page);
+        self.is_loading = true;
+        self.load_error = None;
+        cx.notify();
+
+        let client = cx.http_client();
+        let selectors = if is_current {
+            self.selectors.clone()
+        } else {
+            Arc::new(Selectors::new(SelectorOverrides::for_board(fid)))
+        };
+        let needs_head = page > 1 && !is_current;
+        let url = url.clone();
+        let load = {
+            let url = url.clone();
+            let selectors = selectors.clone();
+            async move {
+                let load = async |selectors: &Selectors, no_cache| {
+                    if needs_head {
+                        Self::load_detail_with_head(
+                            client.clone(),
+                            selectors,
+                            url.clone(),
+                            page,
+                            no_cache,
+                        )
+                        .await
+                    } else {
+                        Self::load_detail(client.clone(), selectors, url.clone(), page, no_cache)
+                            .await
+                    }
+                };
+                match load(&selectors, no_cache).await {
+                    Err(error) if fid.is_none() && !is_current && error.is::<ParseError>() => {
+                        let selectors =
+                            Arc::new(Selectors::new(SelectorOverrides::for_board(Some(FIND_FID))));
+                        load(&selectors, false)
+                            .await
+                            .map(|loaded| (loaded, selectors, Some(FIND_FID)))
+                    }
+                    result => result.map(|loaded| (loaded, selectors, fid)),
+                }
+            }
+        };
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            let talk = match Self::take_prefetched(&this, pending, page, cx).await {
+                Some(prefetched) => Ok((prefetched, selectors, fid)),
+                None => cx.background_spawn(load).await,
+            };
+            if let Some(this) = this.upgrade() {
+                this.update(cx, |this, cx| {
+                    if this.load_generation != generation {
+                        return;
+                    }
+
+                    match talk {
+                        Ok(((update, cached_at), selectors, fid)) => {
+                            this.selectors = selectors;
+                            this.fid = fid;
+                            this.load_success(update, page, cached_at, cx);
+                        }
+                        Err(error) => this.load_failure(error, url, fid, page, cx),
+                    }
+                })
+                .ok();
+            }
+        }));
+    }
+
+    fn load_failure(
+        &mut self,
+        error: anyhow::Error,
+        url: SharedString,
+        fid: Option<u32>,
+        page: u32,
+        cx: &mut Context<Self>,
+    ) {
+        let message = SharedString::from(error.to_string());
+        cx.update_window(self.window_handle, |_, window, cx| {
+            window.push_notification(message.clone(), cx);
+        })
+        .ok();
+        self.load_error = Some((url, fid, page, message));
+        self.pending_floor = None;
+        self.pending_scroll = None;
+        self.is_loading = false;
+        cx.notify();
+    }
+
+    fn load_success(
+        &mut self,
+        update: Update,
+        page: u32,
+        cached_at: Option<NaiveDateTime>,
+        cx: &mut Context<Self>,
+    ) {
+        self.cached_at = cached_at;
+        self.page = page;
+        self.page_input_value = page;
+        cx.update_window(self.window_handle, |_, window, cx| {
+            self.page_state.update(cx, |this, cx| {
+                this.set_value(self.page_input_value.to_string(), window, cx);
+            });
+        })
+        .ok();
+        match update {
+            Update::All(talk_page) => {
+                self.talk = Some(talk_page);
+            }
+            Update::Talk(talks, total_page) => {
+                if let Some(talk) = &mut self.talk {
+                    talk.total_page = talk.total_page.max(total_page);
+                    talk.talks.clear();
+                    talk.talks.extend(talks);
+                }
+            }
+        }
+        if let Some(ref talk_page) = self.talk {
+            self.list_state.reset(talk_page.talks.len() + 2);
+            if let Some(floor) = self.pending_floor.take() {
+                let idx = talk_page
+                    .talks
+                    .iter()
+                    .position(|talk| talk.count >= floor)
+                    .unwrap_or(0);
+                self.list_state.scroll_to(ListOffset {
+                    item_ix: idx + 1,
+                    offset_in_item: px(0.),
+                });
+            } else if let Some(scroll_top) = self.pending_scroll.take() {
+                self.list_state.scroll_to(ListOffset {
+                    item_ix: scroll_top.item_ix.min(talk_page.talks.len() + 1),
+                    ..scroll_top
+                });
+            }
+            self.mark_seen(page, cx);
+            self.prefetch_around(page, cx);
+        }
+        self.is_loading = false;
+        cx.notify();
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        let Some(ref talk) = self.talk else {
+            return;
+        };
+        if !scroll::is_infinite()
+            || self.page >= talk.total_page
+            || self.is_loading
+            || self.is_appending
+            || self.load_error.is_some()
+        {
+            return;
+        }
+
+        let page = self.page + 1;
+        if let Some((update, _)) = self.prefetched.remove(&page) {
+            self.append_success(update, page, cx);
+            return;
+        }
+        let pending = self.prefetch_tasks.remove(&page);
+        self.is_appending = true;
+        cx.notify();
+
+        let generation = self.load_generation;
+        let client = cx.http_client();
+        let selectors = self.selectors.clone();
+        let href = talk.href.clone();
+        let load = async move { Self::load_detail(client, &selectors, href, page, false).await };
+        self.append_task = Some(cx.spawn(async move |this, cx| {
+            let talk = match Self::take_prefetched(&this, pending, page, cx).await {
+                Some(prefetched) => Ok(prefetched),
+                None => cx.background_spawn(load).await,
+            };
+            if let Some(this) = this.upgrade() {
+                this.update(cx, |this, cx| {
+                    if this.load_generation != generation {
+                        return;
+                    }
+
+                    match talk {
+                        Ok((update, _)) => this.append_success(update, page, cx),
+                        Err(error) => this.append_failure(error, cx),
+                    }
+                })
+                .ok();
+            }
+        }));
+    }
+
+    fn append_failure(&mut self, error: anyhow::Error, cx: &mut Context<Self>) {
+        cx.update_window(self.window_handle, |_, window, cx| {
+            window.push_notification(error.to_string(), cx);
+        })
+        .ok();
+        self.is_appending = false;
+        cx.notify();
+    }
+
+    fn append_success(&mut self, update: Update, page: u32, cx: &mut Context<Self>) {
+        self.is_appending = false;
+        let (Some(talk_page), Update::Talk(talks, total_page)) = (&mut self.talk, update) else {
+            cx.notify();
+            return;
+        };
+
+        let shown = talk_page
+            .talks
+            .iter()
+            .map(|talk| talk.count)
+            .collect::<HashSet<_>>();
+        let talks = talks
+            .into_iter()
+            .filter(|talk| !shown.contains(&talk.count))
+            .collect::<Vec<_>>();
+        let start = talk_page.talks.len() + 1;
+        self.list_state.splice(start..start, talks.len());
+        talk_page.total_page = talk_page.total_page.max(total_page);
+        talk_page.talks.extend(talks);
+        self.page = page;
+        self.page_input_value = page;
+        cx.update_window(self.window_handle, |_, window, cx| {
+            self.page_state.update(cx, |this, cx| {
+                this.set_value(self.page_input_value.to_string(), window, cx);
+            });
+        })
+        .ok();
+        self.mark_seen(page, cx);
+        self.prefetch_around(page, cx);
+        cx.notify();
+    }
+
+    fn prefetch_around(&mut self, page: u32, cx: &mut Context<Self>) {
+        self.prefetched
+            .retain(|cached, _| cached.abs_diff(page) == 1);
+        self.prefetch_tasks
+            .retain(|pending, _| pending.abs_diff(page) == 1);
+        let total_page = self.talk.as_ref().map(|talk| talk.total_page).unwrap_or(1);
+        if page < total_page {
+            self.prefetch(page + 1, cx);
+        }
+        if page > 1 {
+            self.prefetch(page - 1, cx);
+        }
+    }
+
+    fn prefetch(&mut self, page: u32, cx: &mut Context<Self>) {
+        let Some(ref talk) = self.talk else {
+            return;
+        };
+        if self.prefetched.contains_key(&page) || self.prefetch_tasks.contains_key(&page) {
+            return;
+        }
+
+        let client = cx.http_client();
+        let selectors = self.selectors.clone();
+        let href = talk.href.clone();
+        let task = cx.background_spawn(prefetch::limited(async move {
+            Self::load_detail(client, &selectors, href, page, false).await
+        }));
+        let task = cx.spawn(async move |this, cx| {
+            let update = task.await;
+            this.update(cx, |this, _| {
+                this.prefetch_tasks.remove(&page);
+                if let Ok(update) = update {
+                    this.prefetched.insert(page, update);
+                }
+            })
+            .ok();
+        });
+        self.prefetch_tasks.insert(page, task);
+    }
+
+    async fn take_prefetched(
+        this: &WeakEntity<Self>,
+        pending: Option<Task<()>>,
+        page: u32,
+        cx: &mut AsyncApp,
+    ) -> Option<(Update, Option<NaiveDateTime>)> {
+        pending?.await;
+
+        this.update(cx, |this, _| this.prefetched.remove(&page))
+            .ok()
+            .flatten()
+    }
+
+    fn clear_prefetch(&mut self) {
+        self.prefetched.clear();
+        self.prefetch_tasks.clear();
+    }
+
+    fn mark_seen(&self, page: u32, cx: &mut Context<Self>) {
+        let Some(ref talk_page) = self.talk else {
+            return;
+        };
+        let Some(max_count) = talk_page.talks.iter().map(|talk| talk.count).max() else {
+            return;
+        };
+
+        let href = talk_page.href.clone();
+        WatchStore::global(cx)
+            .update(cx, |watch_store, cx| {
+                watch_store.mark_seen(&href, page, max_count, cx)
+            })
+            .ok();
+    }
+
+    fn toggle_watch(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref talk) = self.talk else {
+            return;
+        };
+
+        let seen_count = if self.page >= talk.total_page {
+            talk.talks.iter().map(|talk| talk.count).max().unwrap_or(0)
+        } else {
+            0
+        };
+        let watch = Watch::new(
+            talk.title.clone(),
+            talk.href.clone(),
+            self.fid,
+            talk.total_page,
+            seen_count,
+        );
+        let watch_store = WatchStore::global(cx);
+        let result = watch_store.update(cx, |watch_store, cx| watch_store.toggle(watch, cx));
+        if let Err(error) = result {
+            window.push_notification(error.to_string(), cx);
+        }
+    }
+
+    fn quote_talk(&mut self, idx: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(talk) = self.talk.as_ref().and_then(|talk| talk.talks.get(idx)) else {
+            return;
+        };
+
+        let text = talk
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                Content::Text(text) => Some(text.as_ref()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let quote = reply::quote(&talk.author_name, talk.published_at, &text);
+        let value = format!("{quote}{}", self.reply_state.read(cx).value());
+        self.reply_state.update(cx, |state, cx| {
+            state.set_value(value, window, cx);
+            state.focus(window, cx);
+        });
+    }
+
+    fn submit_reply(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref talk) = self.talk else {
+            return;
+        };
+        if self.is_replying {
+            return;
+        }
+        let message = self.reply_state.read(cx).value().to_string();
+        if message.trim().is_empty() {
+            window.push_notification("请输入回复内容", cx);
+            return;
+        }
+        self.is_replying = true;
+        cx.notify();
+
+        let client = cx.http_client();
+        let href = talk.href.clone();
+        let total_page = talk.total_page;
+        cx.spawn_in(window, async move |this, cx| {
+            let result = match reply::fetch_form(&client, &href).await {
+                Ok(form) => reply::post(&client, &form, &message).await,
+                Err(error) => Err(error),
+            };
+            this.update_in(cx, |this, window, cx| {
+                this.is_replying = false;
+                match result {
+                    Ok(page) => {
+                        this.reply_state.update(cx, |state, cx| {
+                            state.set_value("", window, cx);
+                        });
+                        window.push_notification("回复成功", cx);
+                        cx.emit(DetailEvent::Reload(href, page.unwrap_or(total_page)));
+                    }
+                    Err(error) => window.push_notification(error.to_string(), cx),
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn render_composer(&self, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+
+        div()
+            .m_2()
+            .p_2()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .rounded_md()
+            .bg(theme.secondary_hover)
+            .border_1()
+            .border_color(theme.border)
+            .child(div().h(px(100.)).child(TextInput::new(&self.reply_state)))
+            .child(
+                div().flex().justify_end().child(
+                    Button::new("DetailReply")
+                        .icon(IconName::Reply)
+                        .label(if self.is_replying {
+                            "发送中..."
+                        } else {
+                            "回复"
+                        })
+                        .small()
+                        .cursor_pointer()
+                        .disabled(self.is_replying)
+                        .on_click(cx.listener(|this, _, window, cx| this.submit_reply(window, cx))),
+                ),
+            )
+    }
+
+    fn open_export(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.talk.is_none() {
+            return;
+        }
+
+        let detail = cx.entity();
+        window.open_modal(cx, move |modal, _, cx| {
+            modal
+                .title("导出帖子")
+                .child(Self::render_export(&detail, cx))
+        });
+    }
+
+    fn render_export(detail: &Entity<Self>, cx: &App) -> impl IntoElement {
+        let this = detail.read(cx);
+        let formats = ExportFormat::all()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, format)| {
+                let detail = detail.clone();
+                Button::new(("ExportFormat", idx))
+                    .label(format.label())
+                    .ghost()
+                    .small()
+                    .cursor_pointer()
+                    .selected(this.export_format == format)
+                    .on_click(move |_, _, cx| {
+                        detail.update(cx, |this, cx| {
+                            this.export_format = format;
+                            cx.notify();
+                        });
+                    })
+            })
+            .collect::<Vec<_>>();
+        let images_detail = detail.clone();
+        let confirm_detail = detail.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(div().flex().gap_1().children(formats))
+            .child(
+                Checkbox::new("ExportImages")
+                    .label("下载图片")
+                    .checked(this.export_images)
+                    .on_click(move |checked: &bool, _, cx| {
+                        let checked = *checked;
+                        images_detail.update(cx, |this, cx| {
+                            this.export_images = checked;
+                            cx.notify();
+                        });
+                    }),
+            )
+            .child(
+                Button::new("ExportConfirm")
+                    .label("导出")
+                    .cursor_pointer()
+                    .disabled(this.is_exporting)
+                    .on_click(move |_, window, cx| {
+                        window.close_modal(cx);
+                        confirm_detail.update(cx, |this, cx| this.export(window, cx));
+                    }),
+            )
+    }
+
+    fn export(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref talk) = self.talk else {
+            return;
+        };
+        if self.is_exporting {
+            return;
+        }
+
+        self.is_exporting = true;
+        window.push_notification("正在导出...", cx);
+        let client = cx.http_client();
+        let selectors = self.selectors.clone();
+        let href = talk.href.clone();
+        let format = self.export_format;
+        let download_images = self.export_images;
+        let task = cx.background_spawn(async move {
+            let talk_page = Self::load_all(client.clone(), &selectors, href).await?;
+            export::export(client, &talk_page, format, download_images).await
+        });
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            if let Some(this) = this.upgrade() {
+                this.update(cx, |this, cx| this.export_finished(result, cx))
+                    .ok();
+            }
+        })
+        .detach();
+    }
+
+    fn export_finished(&mut self, result: anyhow::Result<PathBuf>, cx: &mut Context<Self>) {
+        self.is_exporting = false;
+        cx.notify();
+        let message = match result {
+            Ok(path) => format!("已导出到 {}", path.display()),
+            Err(error) => error.to_string(),
+        };
+        cx.update_window(self.window_handle, |_, window, cx| {
+            window.push_notification(message, cx);
+        })
+        .ok();
+    }
+
+    fn save_image_as(&mut self, src: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let directory = download::download_dir();
+        let path = cx.prompt_for_new_path(&directory, Some(&download::image_name(&src)));
+        let client = cx.http_client();
+        cx.spawn_in(window, async move |_, cx| {
+            let result = async {
+                let Some(path) = path
+                    .await
+                    .map_err(|error| anyhow::anyhow!("选择路径失败 - {error}"))??
+                else {
+                    return Ok(None);
+                };
+                download::save_image(client, &src, &path).await?;
+
+                anyhow::Ok(Some(path))
+            }
+            .await;
+            let message = match result {
+                Ok(Some(path)) => format!("已保存到 {}", path.display()),
+                Ok(None) => return,
+                Err(error) => error.to_string(),
+            };
+            cx.update(|window, cx| window.push_notification(message, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    fn download_all(&mut self, cx: &mut Context<Self>) {
+        let Some(ref talk) = self.talk else {
+            return;
+        };
+        if self.download_task.is_some() {
+            return;
+        }
+
+        let client = cx.http_client();
+        let selectors = self.selectors.clone();
+        let href = talk.href.clone();
+        self.download_progress = (0, 0);
+        self.download_task = Some(cx.spawn(async move |this, cx| {
+            let result = Self::download_images(this.clone(), client, selectors, href, cx).await;
+            this.update(cx, |this, cx| this.download_finished(result, cx))
+                .ok();
+        }));
+        cx.notify();
+    }
+
+    async fn download_images(
+        this: WeakEntity<Self>,
+        http_client: Arc<dyn HttpClient>,
+        selectors: Arc<Selectors>,
+        href: SharedString,
+        cx: &mut AsyncApp,
+    ) -> anyhow::Result<(PathBuf, usize)> {
+        let client = http_client.clone();
+        let talk_page = cx
+            .background_spawn(async move { Self::load_all(client, &selectors, href).await })
+            .await?;
+        let images = download::thread_images(&talk_page);
+        let dir = download::thread_dir(&talk_page).join("images");
+        smol::fs::create_dir_all(&dir)
+            .await
+            .map_err(|error| anyhow::anyhow!("创建目录失败 - {error}"))?;
+
+        let total = images.len();
+        this.update(cx, |this, cx| {
+            this.download_progress = (0, total);
+            cx.notify();
+        })?;
+        let mut saved = 0;
+        for (idx, image) in images.iter().enumerate() {
+            let path = dir.join(image.file_name());
+            if download::save_image(http_client.clone(), &image.src, &path)
+                .await
+                .is_ok()
+            {
+                saved += 1;
+            }
+            this.update(cx, |this, cx| {
+                this.download_progress = (idx + 1, total);
+                cx.notify();
+            })?;
+        }
+
+        Ok((dir, saved))
+    }
+
+    fn download_finished(
+        &mut self,
+        result: anyhow::Result<(PathBuf, usize)>,
+        cx: &mut Context<Self>,
+    ) {
+        self.download_task = None;
+        cx.notify();
+        let message = match result {
+            Ok((dir, saved)) => format!("已下载 {saved} 张图片到 {}", dir.display()),
+            Err(error) => error.to_string(),
+        };
+        cx.update_window(self.window_handle, |_, window, cx| {
+            window.push_notification(message, cx);
+        })
+        .ok();
+    }
+
+    fn cancel_download(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.download_task.take().is_none() {
+            return;
+        }
+
+        window.push_notification("已取消下载", cx);
+        cx.notify();
+    }
+
+    async fn load_all(
+        http_client: Arc<dyn HttpClient>,
+        selectors: &Selectors,
+        href: SharedString,
+    ) -> anyhow::Result<TalkPage> {
+        let (head, _) =
+            Self::load_detail(http_client.clone(), selectors, href.clone(), 1, false).await?;
+        let Update::All(mut talk_page) = head else {
+            anyhow::bail!("解析失败");
+        };
+        let mut page = 2;
+        while page <= talk_page.total_page {
+            if let (Update::Talk(talks, total_page), _) =
+                Self::load_detail(http_client.clone(), selectors, href.clone(), page, false).await?
+            {
+                talk_page.total_page = talk_page.total_page.max(total_page);
+                talk_page.talks.extend(talks);
+            }
+            page += 1;
+        }
+
+        Ok(talk_page)
+    }
+
+    async fn load_detail(
+        http_client: Arc<dyn HttpClient>,
+        selectors: &Selectors,
+        href: SharedString,
+        page: u32,
+        no_cache: bool,
+    ) -> anyhow::Result<(Update, Option<NaiveDateTime>)> {
+        let (text, cached_at) = Self::load_text(http_client, &href, page, no_cache).await?;
+        let update = Self::parse_page(href, &text, selectors, page == 1).ok_or(ParseError)?;
+
+        Ok((update, cached_at))
+    }
+
+    async fn load_detail_with_head(
+        http_client: Arc<dyn HttpClient>,
+        selectors: &Selectors,
+        href: SharedString,
+        page: u32,
+        no_cache: bool,
+    ) -> anyhow::Result<(Update, Option<NaiveDateTime>)> {
+        let (head, _) =
+            Self::load_detail(http_client.clone(), selectors, href.clone(), 1, false).await?;
+        let Update::All(mut talk_page) = head else {
+            return Err(ParseError.into());
+        };
+        let (update, cached_at) =
+            Self::load_detail(http_client, selectors, href, page, no_cache).await?;
+        if let Update::Talk(talks, total_page) = update {
+            talk_page.total_page = talk_page.total_page.max(total_page);
+            talk_page.talks = talks;
+        }
+
+        Ok((Update::All(talk_page), cached_at))
+    }
+
+    pub async fn load_latest(
+        http_client: Arc<dyn HttpClient>,
+        href: SharedString,
+        fid: Option<u32>,
+        page: u32,
+    ) -> anyhow::Result<Latest> {
+        let selectors = Selectors::new(SelectorOverrides::for_board(fid));
+        let mut page = page.max(1);
+        loop {
+            let (text, _) = Self::load_text(http_client.clone(), &href, page, true).await?;
+            let html = Html::parse_document(&text);
+            let total_page = Self::parse_total_page(&html, &selectors);
+            if total_page > page {
+                page = total_page;
+                continue;
+            }
+
+            let counts = html
+                .select(&selectors.items)
+                .flat_map(|item| item.select(&selectors.item_count).next())
+                .flat_map(|em| em.text().collect::<String>().parse::<u32>().ok())
+                .collect::<Vec<_>>();
+            let (Some(first_count), Some(max_count)) =
+                (counts.iter().min().copied(), counts.iter().max().copied())
+            else {
+                anyhow::bail!("解析失败");
+            };
+
+            return Ok(Latest {
+                total_page,
+                first_count,
+                max_count,
+            });
+        }
+    }
+
+    async fn load_text(
+        http_client: Arc<dyn HttpClient>,
+        href: &str,
+        page: u32,
+        no_cache: bool,
+    ) -> anyhow::Result<(String, Option<NaiveDateTime>)> {
+        let url = format!("{href}&page={page}");
+        page::fetch(&http_client, |cookie| {
+            let mut request = Request::builder()
+                .method("GET")
+                .uri(&url)
+                .header("Cookie", cookie)
+                .header("Accept-Language", "zh-CN,zh-Hans;q=0.9");
+            if no_cache {
+                request = request.header("Cache-Control", "no-cache");
+            }
+            request
+                .body(AsyncBody::empty())
+                .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))
+        })
+        .await
+    }
+
+    fn parse_total_page(html: &Html, selectors: &Selectors) -> u32 {
+        html.select(&selectors.page)
+            .next()
+            .and_then(|span| span.attr("title"))
+            .map(|title| title.trim().trim_matches(&['共', '頁', ' ']))
+            .and_then(|page| page.parse::<u32>().ok())
+            .unwrap_or(1)
+    }
+
+    fn parse_page(
+        href: SharedString,
+        text: &str,
+        selectors: &Selectors,
+        is_first_page: bool,
+    ) -> Option<Update> {
+        let html = Html::parse_document(text);
+
+        if is_first_page {
+            Self::parse_first_page(href, html, selectors)
+        } else {
+            Self::parse_normal_page(html, selectors)
+        }
+    }
+
+    fn parse_first_page(href: SharedString, html: Html, selectors: &Selectors) -> Option<Update> {
+        let title = html
+            .select(&selectors.title)
+            .next()
+            .and_then(|span| span.text().next())
+            .map(|title| title.trim().to_string())
+            .map(SharedString::from)?;
+        let page = Self::parse_total_page(&html, selectors);
+        let main_author_name = html
+            .select(&selectors.main_author_name)
+            .next()
+            .map(|name| name.text().collect::<String>())
+            .map(SharedString::from)?;
+        let main_author_picture = html
+            .select(&selectors.main_author_picture)
+            .next()
+            .and_then(|img| img.attr("src"))
+            .map(|src| src.to_string())
+            .map(SharedString::from)?;
+        let main_published_at = html
+            .select(&selectors.main_published_at)
+            .next()
+            .map(|span| span.text().collect::<String>())
+            .and_then(|date_time| {
+                NaiveDateTime::parse_from_str(date_time.trim(), "%Y-%m-%d %H:%M:%S").ok()
+            })?;
+        let main_content = html
+            .select(&selectors.main_content)
+            .next()
+            .map(|content| Self::parse_content(content))?;
+        let main_replys = html
+            .select(&selectors.main_replys)
+            .into_iter()
+            .flat_map(|item| Self::parse_reply(item, selectors))
+            .collect();
+        let talk = Talk {
+            author_name: main_author_name.clone(),
+            author_picture: main_author_picture,
+            published_at: main_published_at,
+            count: 1,
+            content: main_content,
+            replys: main_replys,
+        };
+        let mut talk_page = TalkPage {
+            total_page: page,
+            title,
+            author_name: main_author_name,
+            href,
+            talks: vec![talk],
+        };
+        let talks = html
+            .select(&selectors.items)
+            .into_iter()
+            .skip(1)
+            .flat_map(|item| Self::parse_item(item, selectors))
+            .collect::<Vec<_>>();
+        talk_page.talks.extend(talks);
+
+        Some(Update::All(talk_page))
+    }
+
+    fn parse_normal_page(html: Html, selectors: &Selectors) -> Option<Update> {
+        let total_page = Self::parse_total_page(&html, selectors);
+        let talks = html
+            .select(&selectors.items)
+            .into_iter()
+            .flat_map(|item| Self::parse_item(item, selectors))
+            .collect();
+
+        Some(Update::Talk(talks, total_page))
+    }
+
+    fn parse_item(item: ElementRef, selectors: &Selectors) -> Option<Talk> {
+        let name = item
+            .select(&selectors.item_name)
+            .next()
+            .map(|a| a.text().collect::<String>())
+            .map(SharedString::from)?;
+        let picture = item
+            .select(&selectors.item_picture)
+            .next()
+            .and_then(|img| img.attr("src"))
+            .map(|src| src.to_string())
+            .map(SharedString::from)?;
+        let published_at = item
+            .select(&selectors.item_published_at)
+            .next()
+            .and_then(|span| span.attr("title"))
+            .and_then(|date_time| {
+                NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M:%S").ok()
+            })
+            .or_else(|| {
+                item.select(&selectors.item_published_at_normal)
+                    .next()
+                    .map(|em| em.text().collect::<String>())
+                    .and_then(|date_time| {
+                        let date_time = date_time
+                            .trim()
+                            .trim_start_matches(&[' ', '發', '表', '於']);
+                        NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M:%S").ok()
+                    })
+            })?;
+        let count = item
+            .select(&selectors.item_count)
+            .next()
+            .map(|em| em.text().collect::<String>())
+            .and_then(|text| text.parse::<u32>().ok())?;
+        let content = item
+            .select(&selectors.item_content)
+            .next()
+            .map(|content| Self::parse_content(content))?;
+        let replys = item
+            .select(&selectors.item_replys)
+            .into_iter()
+            .flat_map(|item| Self::parse_reply(item, selectors))
+            .collect();
+        let talk = Talk {
+            author_name: name,
+            author_picture: picture,
+            published_at,
+            count,
+            content,
+            replys,
+        };
+
+        Some(talk)
+    }
+
+    fn parse_content(content: ElementRef) -> Vec<Content> {
+        let mut contents = Vec::new();
+        Self::parse_inner(*content, &mut contents);
+
+        contents
+    }
+
+    fn parse_inner(node: NodeRef<Node>, contents: &mut Vec<Content>) {
+        for node in node.children() {
+            let value = node.value();
+            match value {
+                Node::Text(text) => {
+                    let text = text.trim();
+                    if text.is_empty() {
+                        continue;
+                    }
+                    let text = text.to_string();
+                    contents.push(Content::Text(SharedString::from(text)));
+                }
+                Node::Element(element) => {
+                    let name = element.name();
+                    match name {
+                        "img" => {
+                            let Some(src) = element.attr("src") else {
+                                continue;
+                            };
+                            if !src.starts_with("http") {
+                                continue;
+                            }
+
+                            let src = src.to_string();
+                            contents.push(Content::Image(SharedString::from(src)));
+                        }
+                        "blockquote" => {
+                            let Some(quote) = Self::parse_blockquote(node) else {
+                                continue;
+                            };
+                            contents.push(quote);
+                        }
+                        _ => {
+                            Self::parse_inner(node, contents);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn parse_blockquote(node: NodeRef<Node>) -> Option<Content> {
+        let mut texts = Vec::new();
+        for child in node.descendants() {
+            match child.value().as_text() {
+                Some(text) => {
+                    texts.push(text.to_string());
+                }
+                None => continue,
+            }
+        }
+
+        let info = texts.get(0)?.split(' ').collect::<Vec<_>>();
+        let content = texts.get(1)?.trim().to_string();
+        let name = info.get(0)?.to_string();
+        let date = info.get(2)?;
+        let time = info.get(3)?;
+        let date_time = format!("{date} {time}");
+        let date_time = NaiveDateTime::parse_from_str(&date_time, "%Y-%m-%d %H:%M").ok()?;
+
+        Some(Content::Quote(
+            SharedString::from(name),
+            date_time,
+            SharedString::from(content),
+        ))
+    }
+
+    fn parse_reply(item: ElementRef, selectors: &Selectors) -> Option<Reply> {
+        let reply_name = item
+            .select(&selectors.reply_name)
+            .next()
+            .map(|a| a.text().collect::<String>())
+            .map(SharedString::from)?;
+        let reply_picture = item
+            .select(&selectors.reply_picture)
+            .next()
+            .and_then(|img| img.attr("src"))
+            .map(|src| src.to_string())
+            .map(SharedString::from)?;
+        let reply_published_at = item
+            .select(&selectors.reply_published_at)
+            .next()
+            .and_then(|span| span.attr("title"))
+            .and_then(|date_time| NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").ok())
+            .or_else(|| {
+                item.select(&selectors.reply_published_at_normal)
+                    .next()
+                    .map(|span| span.text().collect::<String>())
+                    .and_then(|date_time| {
+                        let date_time = date_time
+                            .trim()
+                            .trim_start_matches(&[' ', '發', '表', '於']);
+                        NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").ok()
+                    })
+            })?;
+        let reply_content = item
+            .select(&selectors.reply_content)
+            .next()
+            .and_then(|div| div.text().next())
+            .map(|content| content.trim().to_string())
+            .map(SharedString::from)?;
+        let reply = Reply {
+            author_name: reply_name,
+            author_picture: reply_picture,
+            published_at: reply_published_at,
+            content: reply_content,
+        };
+
+        Some(reply)
+    }
+
+    fn load_circle() -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .justify_center()
+            .items_center()
+            .child(Indicator::new().large().icon(IconName::LoaderCircle))
+    }
+
+    fn render_retry(
+        url: SharedString,
+        fid: Option<u32>,
+        page: u32,
+        message: SharedString,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .justify_center()
+            .items_center()
+            .gap_2()
+            .child(Label::new(message).text_sm())
+            .child(
+                Button::new("DetailRetry")
+                    .label("重试")
+                    .small()
+                    .cursor_pointer()
+                    .on_click(cx.listener(move |_, _, _, cx| {
+                        cx.emit(DetailEvent::Load(url.clone(), fid, page));
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn render_content(&self, _: &Window, cx: &Context<Self>) -> impl IntoElement {
+        list(
+            self.list_state.clone(),
+            cx.processor(|this, idx, window, cx| this.render_item(idx, window, cx)),
+        )
+        .size_full()
+    }
+
+    fn render_content_item(content: &Content, cx: &Context<Self>) -> AnyElement {
+        let theme = cx.theme();
+
+        match content {
+            Content::Text(text) => Label::new(text).pt_2().into_any_element(),
+            Content::Image(src) => {
+                let clicked = src.clone();
+                let saved = src.clone();
+                div()
+                    .pt_2()
+                    .relative()
+                    .cursor_pointer()
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, window, cx| {
+                            this.open_image(&clicked, window, cx)
+                        }),
+                    )
+                    .child(img(src.clone()).max_w_full().rounded_md())
+                    .child(
+                        div()
+                            .absolute()
+                            .top_3()
+                            .right_1()
+                            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                            .child(
+                                Button::new(src.clone())
+                                    .icon(IconName::Download)
+                                    .tooltip("图片另存为")
+                                    .ghost()
+                                    .small()
+                                    .cursor_pointer()
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.save_image_as(saved.clone(), window, cx)
+                                    })),
+                            ),
+                    )
+                    .into_any_element()
+            }
+            Content::Quote(name, date_time, content) => div()
+                .pt_2()
+                .child(
+                    div()
+                        .p_2()
+                        .gap_1()
+                        .rounded_md()
+                        .bg(theme.secondary_active)
+                        .child(
+                            div()
+                                .flex()
+                                .gap_1()
+                                .child(
+                                    Label::new(name)
+                                        .text_color(theme.blue)
+                                        .font_light()
+                                        .text_sm(),
+                                )
+                                .child(
+                                    Label::new(date_time.format("@ %Y-%m-%d %H:%M").to_string())
+                                        .text_color(theme.yellow)
+                                        .font_light()
+                                        .text_sm(),
+                                ),
+                        )
+                        .child(Label::new(content)),
+                )
+                .into_any_element(),
+        }
+    }
+
+    fn open_image(&mut self, src: &SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref talk_page) = self.talk else {
+            return;
+        };
+
+        let images = talk_page
+            .talks
+            .iter()
+            .flat_map(|talk| talk.content.iter())
+            .filter_map(|content| match content {
+                Content::Image(src) => Some(src.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let index = images.iter().position(|image| image == src).unwrap_or(0);
+        ImageViewer::open(images, index, window, cx);
+    }
+
+    fn render_reply(reply: &Reply, cx: &Context<Self>, is_first: bool) -> impl IntoElement {
+        let theme = cx.theme();
+
+        div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .when(!is_first, |this| this.pt_2())
+            .child(Avatar::new().src(reply.author_picture.clone()).small())
+            .child(
+                Label::new(reply.author_name.clone())
+                    .text_color(theme.blue)
+                    .font_light()
+                    .text_sm(),
+            )
+            .child(
+                Label::new(reply.published_at.format("@ %Y-%m-%d %H:%M").to_string())
+                    .text_color(theme.yellow)
+                    .font_light()
+                    .text_sm(),
+            )
+            .child(Label::new(reply.content.clone()).overflow_hidden())
+    }
+
+    fn render_talk(&self, idx: usize, talk: &Talk, cx: &mut Context<Self>) -> impl IntoElement {
+        let can_reply = Account::global(cx).read(cx).username().is_some();
+        let quote_button = can_reply.then(|| {
+            Button::new(("DetailQuote", idx))
+                .icon(IconName::Quote)
+                .tooltip("引用")
+                .ghost()
+                .small()
+                .cursor_pointer()
+                .on_click(cx.listener(move |this, _, window, cx| this.quote_talk(idx, window, cx)))
+        });
+        let theme = cx.theme();
+        let contents = talk
+            .content
+            .iter()
+            .map(|item| Self::render_content_item(item, cx))
+            .collect::<Vec<_>>();
+        let replys = talk
+            .replys
+            .iter()
+            .enumerate()
+            .map(|(idx, reply)| Self::render_reply(reply, cx, idx == 0))
+            .collect::<Vec<_>>();
+
+        div()
+            .p_2()
+            .rounded_md()
+            .bg(theme.secondary_hover)
+            .border_1()
+            .border_color(theme.border)
+            .child(
+                div()
+                    .flex()
+                    .items_start()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .gap_1()
+                            .child(Avatar::new().src(talk.author_picture.clone()))
+                            .child(
+                                div()
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .gap_1()
+                                            .child(
+                                                Label::new(talk.author_name.clone())
+                                                    .text_color(theme.blue)
+                                                    .font_light()
+                                                    .text_sm(),
+                                            )
+                                            .child(
+                                                Label::new(
+                                                    talk.published_at
+                                                        .format("@ %Y-%m-%d %H:%M:%S")
+                                                        .to_string(),
+                                                )
+                                                .text_color(theme.yellow)
+                                                .font_light()
+                                                .text_sm(),
+                                            ),
+                                    )
+                                    .child(
+                                        Label::new(format!("#{}", talk.count))
+                                            .text_color(theme.primary_hover)
+                                            .font_light()
+                                            .text_sm(),
+                                    ),
+                            ),
+                    )
+                    .children(quote_button),
+            )
+            .children(contents)
+            .when(!replys.is_empty(), |this| {
+                this.child(
+                    div()
+                        .p_2()
+                        .mt_2()
+                        .rounded_md()
+                        .bg(theme.secondary_active)
+                        .children(replys),
+                )
+            })
+    }
+
+    fn render_item(&self, idx: usize, window: &Window, cx: &mut Context<Self>) -> AnyElement {
+        let Some(ref talk) = self.talk else {
+            return div().into_any_element();
+        };
+
+        let item = if idx == 0 {
+            self.render_title(window, cx).into_any_element()
+        } else if idx == talk.talks.len() + 1 {
+            if scroll::is_infinite() {
+                self.render_more(cx).into_any_element()
+            } else {
+                self.render_pager(cx).into_any_element()
+            }
+        } else {
+            let talk = &talk.talks[idx - 1];
+            self.render_talk(idx - 1, talk, cx).into_any_element()
+        };
+
+        div()
+            .pt_2()
+            .px_2()
+            .when(idx == talk.talks.len() + 1, |div| div.pb_2())
+            .child(item)
+            .into_any_element()
+    }
+
+    fn toggle_bookmark(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref talk) = self.talk else {
+            return;
+        };
+
+        let bookmark = Bookmark::new(
+            talk.title.clone(),
+            talk.href.clone(),
+            self.fid,
+            talk.author_name.clone(),
+        );
+        let bookmark_store = BookmarkStore::global(cx);
+        let result =
+            bookmark_store.update(cx, |bookmark_store, cx| bookmark_store.toggle(bookmark, cx));
+        if let Err(error) = result {
+            window.push_notification(error.to_string(), cx);
+        }
+    }
+
+    fn render_title(&self, _: &Window, cx: &Context<Self>) -> impl IntoElement {
+        let Some(ref talk) = self.talk else {
+            return div();
+        };
+        let is_bookmarked = BookmarkStore::global(cx).read(cx).contains(&talk.href);
+        let is_watching = WatchStore::global(cx).read(cx).is_watching(&talk.href);
+        let theme = cx.theme();
+
+        div()
+            .p_2()
+            .flex()
+            .items_start()
+            .justify_between()
+            .gap_1()
+            .rounded_md()
+            .bg(theme.secondary_hover)
+            .border_1()
+            .border_color(theme.border)
+            .child(Label::new(talk.title.clone()).font_semibold().text_lg())
+            .child(
+                div()
+                    .flex()
+                    .gap_1()
+                    .items_center()
+                    .when_some(self.cached_at, |this, cached_at| {
+                        this.child(
+                            Label::new(cached_at.format("缓存于 %m-%d %H:%M").to_string())
+                                .text_color(theme.yellow)
+                                .font_light()
+                                .text_sm(),
+                        )
+                    })
+                    .when_some(
+                        self.download_task.as_ref().map(|_| self.download_progress),
+                        |this, (done, total)| {
+                            this.child(Label::new(format!("{done}/{total}")).text_sm())
+                                .child(
+                                    Button::new("DetailDownloadCancel")
+                                        .label("取消")
+                                        .ghost()
+                                        .small()
+                                        .cursor_pointer()
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.cancel_download(window, cx)
+                                        })),
+                                )
+                        },
+                    )
+                    .child(
+                        Button::new("DetailDownload")
+                            .icon(IconName::Images)
+                            .tooltip("下载全部图片")
+                            .ghost()
+                            .small()
+                            .cursor_pointer()
+                            .disabled(self.download_task.is_some())
+                            .on_click(cx.listener(|this, _, _, cx| this.download_all(cx))),
+                    )
+                    .child(
+                        Button::new("DetailExport")
+                            .icon(IconName::Download)
+                            .ghost()
+                            .small()
+                            .cursor_pointer()
+                            .disabled(self.is_exporting)
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.open_export(window, cx)),
+                            ),
+                    )
+                    .child(
+                        Button::new("DetailWatch")
+                            .icon(if is_watching {
+                                IconName::BellRing
+                            } else {
+                                IconName::Bell
+                            })
+                            .ghost()
+                            .small()
+                            .cursor_pointer()
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.toggle_watch(window, cx)),
+                            ),
+                    )
+                    .child(
+                        Button::new("DetailBookmark")
+                            .icon(if is_bookmarked {
+                                IconName::BookmarkCheck
+                            } else {
+                                IconName::Bookmark
+                            })
+                            .ghost()
+                            .small()
+                            .cursor_pointer()
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.toggle_bookmark(window, cx)),
+                            ),
+                    ),
+            )
+    }
+
+    fn render_more(&self, cx: &Context<Self>) -> impl IntoElement {
+        let max_page = self.talk.as_ref().map(|talk| talk.total_page).unwrap_or(1);
+        let theme = cx.theme();
+
+        div()
+            .w_full()
+            .h(PAGER_HEIGHT)
+            .flex()
+            .justify_center()
+            .items_center()
+            .when(self.is_appending, |this| {
+                this.child(Indicator::new().small().icon(IconName::LoaderCircle))
+            })
+            .when(!self.is_appending && self.page >= max_page, |this| {
+                this.child(
+                    Label::new("没有更多了")
+                        .text_color(theme.muted_foreground)
+                        .text_sm(),
+                )
+            })
+    }
+
+    fn render_pager(&self, cx: &Context<Self>) -> impl IntoElement {
+        let max_page = self.talk.as_ref().map(|talk| talk.total_page).unwrap_or(1);
+
+        div()
+            .w_full()
+            .h(PAGER_HEIGHT)
+            .flex()
+            .items_center()
+            .justify_between()
+            .p_2()
+            .child(
+                Button::new("ForumPrevious")
+                    .icon(IconName::ChevronLeft)
+                    .ghost()
+                    .disabled(self.page <= 1)
+                    .when(self.page > 1, |div| div.cursor_pointer())
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        let Some(ref talk) = this.talk else {
+                            return;
+                        };
+
+                        let page = this.page - 1;
+                        cx.emit(DetailEvent::Load(talk.href.clone(), this.fid, page));
+                        cx.notify();
+                    })),
+            )
+            .child(
+                div()
+                    .h_full()
+                    .w(px(200.))
+                    .flex()
+                    .justify_center()
+                    .items_center()
+                    .gap_1()
+                    .child(Label::new("第"))
+                    .child(NumberInput::new(&self.page_state))
+                    .child(Label::new("页,"))
+                    .child(Label::new(format!("共 {max_page} 页"))),
+            )
+            .child(
+                Button::new("ForumNext")
+                    .icon(IconName::ChevronRight)
+                    .ghost()
+                    .cursor_pointer()
+                    .disabled(self.page >= max_page)
+                    .when(self.page < max_page, |div| div.cursor_pointer())
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        let Some(ref talk) = this.talk else {
+                            return;
+                        };
+
+                        let page = this.page + 1;
+                        cx.emit(DetailEvent::Load(talk.href.clone(), this.fid, page));
+                        cx.notify();
+                    })),
+            )
+    }
+}
+
+impl Render for Detail {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match self.load_error.clone() {
+            Some((url, fid, page, message)) => {
+                Self::render_retry(url, fid, page, message, cx).into_any_element()
+            }
+            None => self.render_content(window, cx).into_any_element(),
+        };
+        let can_reply = self.talk.is_some()
+            && self.load_error.is_none()
+            && Account::global(cx).read(cx).username().is_some();
+        let composer = can_reply.then(|| self.render_composer(cx));
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .flex()
+            .flex_col()
+            .when(self.is_loading, |div| div.child(Self::load_circle()))
+            .when(!self.is_loading, |this| {
+                this.child(div().flex_1().min_h_0().child(content))
+                    .children(composer)
+            })
+    }
+}
+
+impl Focusable for Detail {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+pub enum DetailEvent {
+    Load(SharedString, Option<u32>, u32),
+    Reload(SharedString, u32),
+    LoadFloor(SharedString, Option<u32>, u32, u32),
+    Restore(ThreadLocation),
+    Close,
+    Opened(Option<ThreadLocation>),
+}
+
+#[derive(Clone)]
+pub struct ThreadLocation {
+    tab: EntityId,
+    href: SharedString,
+    fid: Option<u32>,
+    page: u32,
+    scroll_top: ListOffset,
+}
+
+impl ThreadLocation {
+    pub fn tab(&self) -> EntityId {
+        self.tab
+    }
+}
+
+pub struct Latest {
+    pub total_page: u32,
+    pub first_count: u32,
+    pub max_count: u32,
+}
+
+impl Latest {
+    pub fn page_of(&self, floor: u32) -> u32 {
+        if floor >= self.first_count || self.total_page <= 1 {
+            return self.total_page;
+        }
+
+        let per_page = (self.first_count.saturating_sub(1) / (self.total_page - 1)).max(1);
+        ((floor.saturating_sub(1) / per_page) + 1).min(self.total_page)
+    }
+}
+
+enum Update {
+    All(TalkPage),
+    Talk(Vec<Talk>, u32),
+}
+
+#[derive(Debug)]
+struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("解析失败")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl EventEmitter<DetailEvent> for Detail {}
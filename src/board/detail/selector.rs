@@ -1,5 +1,7 @@
 use scraper::Selector;
 
+use crate::board::info::FIND_FID;
+
 pub struct Selectors {
     pub title: Selector,
     pub page: Selector,
@@ -23,12 +25,46 @@ pub struct Selectors {
     pub item_replys: Selector,
 }
 
+#[derive(Clone, Copy)]
+pub struct SelectorOverrides {
+    pub main_content: &'static str,
+    pub item_picture: &'static str,
+}
+
+impl SelectorOverrides {
+    pub fn for_board(fid: Option<u32>) -> Self {
+        match fid {
+            Some(FIND_FID) => Self::find(),
+            _ => Self::default(),
+        }
+    }
+
+    fn find() -> Self {
+        Self {
+            main_content: "#postlist > div.nthread_firstpostbox > table.nthread_firstpost > tbody > tr:nth-child(1) > td > div > div > div:nth-child(2) > table > tbody > tr > td.t_f",
+            item_picture: "table.plhin > tbody > tr:nth-child(1) > td.pls > div.pls.favatar > div > div.avatar > a > img",
+        }
+    }
+}
+
+impl Default for SelectorOverrides {
+    fn default() -> Self {
+        Self {
+            main_content: "#postlist > div.nthread_firstpostbox > table.nthread_firstpost > tbody > tr:nth-child(1) > td > div > div > div:nth-child(1) > table > tbody > tr > td.t_f",
+            item_picture: "table.plhin > tbody > tr:nth-child(1) > td.pls > div.pls.favatar > div:nth-child(2) > div > a > img",
+        }
+    }
+}
+
 impl Selectors {
-    pub fn new() -> Self {
+    pub fn new(overrides: SelectorOverrides) -> Self {
         macro_rules! parse_selector {
-            ($s:expr) => {
+            ($s:literal) => {
                 Selector::parse($s).expect(concat!("Failed to parse selector: ", $s))
             };
+            ($s:expr) => {
+                Selector::parse($s).unwrap_or_else(|_| panic!("Failed to parse selector: {}", $s))
+            };
         }
 
         Self {
@@ -45,9 +81,7 @@ impl Selectors {
             main_published_at: parse_selector!(
                 "#postlist > div.nthread_info.cl > div > div > span:nth-child(2)"
             ),
-            main_content: parse_selector!(
-                "#postlist > div.nthread_firstpostbox > table.nthread_firstpost > tbody > tr:nth-child(1) > td > div > div > div:nth-child(2) > table > tbody > tr > td.t_f"
-            ),
+            main_content: parse_selector!(overrides.main_content),
             main_replys: parse_selector!(
                 "#postlist > div.nthread_firstpostbox > table.nthread_firstpost > tbody > tr:nth-child(1) > td > div > div > div.cm > div.pstl"
             ),
@@ -60,9 +94,7 @@ impl Selectors {
             item_name: parse_selector!(
                 "table.plhin > tbody > tr:nth-child(1) > td.plc > div.pi > div > div.authi > a.xw1"
             ),
-            item_picture: parse_selector!(
-                "table.plhin > tbody > tr:nth-child(1) > td.pls > div.pls.favatar > div > div.avatar > a > img"
-            ),
+            item_picture: parse_selector!(overrides.item_picture),
             item_published_at: parse_selector!(
                 "table.plhin > tbody > tr:nth-child(1) > td.plc > div.pi > div > div.authi > em > span"
             ),
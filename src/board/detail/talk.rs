@@ -1,13 +1,17 @@
 use chrono::NaiveDateTime;
 use gpui::SharedString;
+use serde::Serialize;
 
+#[derive(Serialize)]
 pub struct TalkPage {
     pub total_page: u32,
     pub title: SharedString,
+    pub author_name: SharedString,
     pub href: SharedString,
     pub talks: Vec<Talk>,
 }
 
+#[derive(Serialize)]
 pub struct Talk {
     pub author_name: SharedString,
     pub author_picture: SharedString,
@@ -17,12 +21,14 @@ pub struct Talk {
     pub replys: Vec<Reply>,
 }
 
+#[derive(Serialize)]
 pub enum Content {
     Text(SharedString),
     Image(SharedString),
     Quote(SharedString, NaiveDateTime, SharedString),
 }
 
+#[derive(Serialize)]
 pub struct Reply {
     pub author_name: SharedString,
     pub author_picture: SharedString,
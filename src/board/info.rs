@@ -0,0 +1,65 @@
+use gpui::SharedString;
+use serde::{Deserialize, Serialize};
+
+use crate::icon::IconName;
+use crate::store;
+
+const FORUM_FID: u32 = 2;
+pub const FIND_FID: u32 = 36;
+const PINNED_BOARDS: &str = "pinned_boards";
+
+#[derive(Clone)]
+pub struct BoardInfo {
+    pub fid: u32,
+    pub name: SharedString,
+    pub icon: Option<IconName>,
+}
+
+impl BoardInfo {
+    pub fn new(fid: u32, name: impl Into<SharedString>) -> Self {
+        let icon = match fid {
+            FORUM_FID => Some(IconName::House),
+            FIND_FID => Some(IconName::BookMarked),
+            _ => None,
+        };
+
+        Self {
+            fid,
+            name: name.into(),
+            icon,
+        }
+    }
+
+    pub fn builtin() -> Vec<Self> {
+        vec![Self::new(FORUM_FID, "论坛"), Self::new(FIND_FID, "发现")]
+    }
+
+    pub fn load_pinned() -> Vec<Self> {
+        store::load::<Vec<PinnedBoard>>(PINNED_BOARDS)
+            .map(|boards| {
+                boards
+                    .into_iter()
+                    .map(|board| Self::new(board.fid, board.name))
+                    .collect()
+            })
+            .unwrap_or_else(Self::builtin)
+    }
+
+    pub fn save_pinned(boards: &[Self]) -> anyhow::Result<()> {
+        let boards = boards
+            .iter()
+            .map(|board| PinnedBoard {
+                fid: board.fid,
+                name: board.name.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        store::save(PINNED_BOARDS, &boards)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PinnedBoard {
+    fid: u32,
+    name: String,
+}
@@ -0,0 +1,129 @@
+mod bookmarks;
+mod detail;
+mod info;
+mod picker;
+mod prefetch;
+mod search;
+mod summary;
+mod tabs;
+mod watching;
+
+use detail::{DetailEvent, ThreadLocation};
+use gpui::{
+    App, AppContext as _, Context, Entity, EntityId, FocusHandle, InteractiveElement, IntoElement,
+    ParentElement as _, Render, Styled as _, Window, div,
+};
+use summary::{ListLocation, Source, Summary, SummaryEvent};
+
+pub use bookmarks::Bookmarks;
+pub use detail::{Detail, Latest};
+pub use info::BoardInfo;
+pub use picker::{BoardPicker, BoardPickerEvent};
+pub use search::Search;
+pub use tabs::{DetailTabs, TabsEvent};
+pub use watching::Watching;
+
+pub struct Board {
+    info: BoardInfo,
+    summary: Entity<Summary>,
+    tabs: Entity<DetailTabs>,
+    focus_handle: FocusHandle,
+}
+
+impl Board {
+    pub fn new(info: BoardInfo, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let summary = cx.new(|cx| Summary::new(Source::board(info.fid), window, cx));
+        summary.update(cx, |_, cx| {
+            cx.emit(SummaryEvent::Load(1));
+            cx.notify();
+        });
+        cx.subscribe_in(&summary, window, |this, _, event, window, cx| match event {
+            SummaryEvent::LoadDetail(detail_url, fid) => {
+                this.tabs.update(cx, |tabs, cx| {
+                    tabs.send(DetailEvent::Load(detail_url.clone(), *fid, 1), cx)
+                });
+            }
+            SummaryEvent::LoadDetailInNewTab(detail_url, fid) => {
+                this.tabs.update(cx, |tabs, cx| {
+                    tabs.open_in_new_tab(DetailEvent::Load(detail_url.clone(), *fid, 1), window, cx)
+                });
+            }
+            _ => {}
+        })
+        .detach();
+        let tabs = cx.new(|cx| DetailTabs::new(window, cx));
+
+        Self {
+            info,
+            summary,
+            tabs,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn info(&self) -> &BoardInfo {
+        &self.info
+    }
+
+    pub fn tabs(&self) -> &Entity<DetailTabs> {
+        &self.tabs
+    }
+
+    pub fn view_state(&self, cx: &App) -> ViewState {
+        ViewState::capture(Some(&self.summary), &self.tabs, cx)
+    }
+
+    pub fn restore(&mut self, state: ViewState, window: &mut Window, cx: &mut Context<Self>) {
+        state.restore(Some(&self.summary), &self.tabs, window, cx);
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ViewState {
+    list: Option<ListLocation>,
+    tab: Option<EntityId>,
+    thread: Option<ThreadLocation>,
+}
+
+impl ViewState {
+    pub fn with_thread(self, thread: Option<ThreadLocation>) -> Self {
+        Self { thread, ..self }
+    }
+
+    fn capture(summary: Option<&Entity<Summary>>, tabs: &Entity<DetailTabs>, cx: &App) -> Self {
+        Self {
+            list: summary.and_then(|summary| summary.read(cx).location()),
+            tab: Some(tabs.read(cx).active().entity_id()),
+            thread: tabs.read(cx).active().read(cx).location(),
+        }
+    }
+
+    fn restore(
+        self,
+        summary: Option<&Entity<Summary>>,
+        tabs: &Entity<DetailTabs>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        if let (Some(summary), Some(list)) = (summary, self.list) {
+            summary.update(cx, |_, cx| {
+                cx.emit(SummaryEvent::Restore(list));
+                cx.notify();
+            });
+        }
+        let tab = self.thread.as_ref().map(ThreadLocation::tab).or(self.tab);
+        tabs.update(cx, |tabs, cx| tabs.restore(tab, self.thread, window, cx));
+    }
+}
+
+impl Render for Board {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .track_focus(&self.focus_handle)
+            .w_full()
+            .h_full()
+            .flex()
+            .child(self.summary.clone())
+            .child(self.tabs.clone())
+    }
+}
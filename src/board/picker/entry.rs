@@ -0,0 +1,8 @@
+use gpui::SharedString;
+
+pub struct BoardEntry {
+    pub fid: u32,
+    pub name: SharedString,
+    pub threads: u32,
+    pub today_posts: u32,
+}
@@ -0,0 +1,298 @@
+mod entry;
+mod selector;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use entry::BoardEntry;
+use gpui::{
+    AnyWindowHandle, App, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement as _, IntoElement, ParentElement as _, Pixels, Render, SharedString,
+    StatefulInteractiveElement as _, Styled as _, Window, div, px,
+};
+use gpui_component::checkbox::Checkbox;
+use gpui_component::indicator::Indicator;
+use gpui_component::label::Label;
+use gpui_component::{ActiveTheme as _, ContextModal as _, Sizable as _};
+use http_client::{AsyncBody, HttpClient, Request};
+use scraper::{ElementRef, Html, Selector};
+use selector::Selectors;
+
+use super::BoardInfo;
+use crate::icon::IconName;
+use crate::mirror;
+use crate::page;
+
+const PICKER_HEIGHT: Pixels = px(500.);
+
+pub struct BoardPicker {
+    selectors: Arc<Selectors>,
+    entries: Vec<BoardEntry>,
+    pinned: HashSet<u32>,
+    is_loading: bool,
+    focus_handle: FocusHandle,
+    window_handle: AnyWindowHandle,
+}
+
+impl BoardPicker {
+    pub fn new(pinned: HashSet<u32>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let entity = cx.entity();
+        cx.subscribe(&entity, Self::on_event).detach();
+
+        Self {
+            selectors: Arc::new(Selectors::new()),
+            entries: Vec::new(),
+            pinned,
+            is_loading: false,
+            focus_handle: cx.focus_handle(),
+            window_handle: window.window_handle(),
+        }
+    }
+
+    fn on_event(&mut self, _: Entity<Self>, evt: &BoardPickerEvent, cx: &mut Context<Self>) {
+        match evt {
+            BoardPickerEvent::Load => self.event_load(cx),
+            _ => {}
+        }
+    }
+
+    fn event_load(&mut self, cx: &mut Context<Self>) {
+        if self.is_loading {
+            return;
+        }
+        self.is_loading = true;
+        cx.notify();
+
+        let client = cx.http_client();
+        let selectors = self.selectors.clone();
+        let task = cx.background_spawn(async move { Self::load_index(client, &selectors).await });
+        cx.spawn(async move |this, cx| {
+            let entries = task.await;
+            if let Some(this) = this.upgrade() {
+                this.update(cx, |this, cx| match entries {
+                    Ok(entries) => this.load_success(entries, cx),
+                    Err(error) => this.load_failure(error, cx),
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
+    fn load_failure(&mut self, error: anyhow::Error, cx: &mut Context<Self>) {
+        cx.update_window(self.window_handle, |_, window, cx| {
+            window.push_notification(error.to_string(), cx);
+        })
+        .ok();
+        self.is_loading = false;
+        cx.notify();
+    }
+
+    fn load_success(&mut self, entries: Vec<BoardEntry>, cx: &mut Context<Self>) {
+        self.entries.clear();
+        self.entries.extend(entries);
+        self.is_loading = false;
+        cx.notify();
+    }
+
+    async fn load_index(
+        http_client: Arc<dyn HttpClient>,
+        selectors: &Selectors,
+    ) -> anyhow::Result<Vec<BoardEntry>> {
+        let (text, _) = page::fetch(&http_client, |cookie| {
+            Request::builder()
+                .method("GET")
+                .uri(mirror::forum_url("forum.php"))
+                .header("Cookie", cookie)
+                .header("Accept-Language", "zh-CN,zh-Hans;q=0.9")
+                .body(AsyncBody::empty())
+                .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))
+        })
+        .await?;
+
+        let entries = Self::parse_index(&text, selectors);
+        anyhow::ensure!(!entries.is_empty(), "解析失败");
+
+        Ok(entries)
+    }
+
+    fn parse_index(text: &str, selectors: &Selectors) -> Vec<BoardEntry> {
+        let html = Html::parse_document(text);
+        let grid_entries = html.select(&selectors.grid_items).flat_map(|item| {
+            Self::parse_entry(
+                item,
+                &selectors.grid_name,
+                &selectors.grid_today_posts,
+                &selectors.grid_threads,
+            )
+        });
+        let list_entries = html.select(&selectors.list_items).flat_map(|item| {
+            Self::parse_entry(
+                item,
+                &selectors.list_name,
+                &selectors.list_today_posts,
+                &selectors.list_threads,
+            )
+        });
+
+        let mut seen = HashSet::new();
+        grid_entries
+            .chain(list_entries)
+            .filter(|entry| seen.insert(entry.fid))
+            .collect()
+    }
+
+    fn parse_entry(
+        item: ElementRef,
+        name: &Selector,
+        today_posts: &Selector,
+        threads: &Selector,
+    ) -> Option<BoardEntry> {
+        let link = item.select(name).next()?;
+        let fid = link.attr("href").and_then(Self::parse_fid)?;
+        let name = link.text().collect::<String>().trim().to_string();
+        if name.is_empty() {
+            return None;
+        }
+        let today_posts = item
+            .select(today_posts)
+            .next()
+            .map(|em| em.text().collect::<String>())
+            .and_then(|text| Self::parse_number(&text))
+            .unwrap_or(0);
+        let threads = item
+            .select(threads)
+            .next()
+            .map(|em| em.text().collect::<String>())
+            .and_then(|text| Self::parse_number(&text))
+            .unwrap_or(0);
+
+        Some(BoardEntry {
+            fid,
+            name: SharedString::from(name),
+            threads,
+            today_posts,
+        })
+    }
+
+    fn parse_fid(href: &str) -> Option<u32> {
+        if let Some((_, query)) = href.split_once('?') {
+            return query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("fid="))
+                .and_then(|fid| fid.parse::<u32>().ok());
+        }
+
+        href.strip_prefix("forum-")
+            .and_then(|rest| rest.split('-').next())
+            .and_then(|fid| fid.parse::<u32>().ok())
+    }
+
+    fn parse_number(text: &str) -> Option<u32> {
+        let digits = text
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect::<String>();
+
+        digits.parse::<u32>().ok()
+    }
+
+    fn on_toggle(&mut self, fid: u32, name: SharedString, checked: bool, cx: &mut Context<Self>) {
+        if checked {
+            self.pinned.insert(fid);
+            cx.emit(BoardPickerEvent::Pin(BoardInfo::new(fid, name)));
+        } else {
+            self.pinned.remove(&fid);
+            cx.emit(BoardPickerEvent::Unpin(fid));
+        }
+        cx.notify();
+    }
+
+    fn load_circle() -> impl IntoElement {
+        div()
+            .w_full()
+            .h(PICKER_HEIGHT)
+            .flex()
+            .justify_center()
+            .items_center()
+            .child(Indicator::new().large().icon(IconName::LoaderCircle))
+    }
+
+    fn render_entry(&self, entry: &BoardEntry, cx: &mut Context<Self>) -> impl IntoElement {
+        let fid = entry.fid;
+        let name = entry.name.clone();
+        let theme = cx.theme();
+
+        div()
+            .p_2()
+            .flex()
+            .items_center()
+            .justify_between()
+            .border_b_1()
+            .border_color(theme.border)
+            .child(
+                Checkbox::new(("BoardPin", fid as usize))
+                    .label(entry.name.clone())
+                    .checked(self.pinned.contains(&fid))
+                    .on_click(cx.listener(move |this, checked: &bool, _, cx| {
+                        this.on_toggle(fid, name.clone(), *checked, cx)
+                    })),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        Label::new(format!("主题 {}", entry.threads))
+                            .text_color(theme.primary_hover)
+                            .font_light()
+                            .text_sm(),
+                    )
+                    .child(
+                        Label::new(format!("今日 {}", entry.today_posts))
+                            .text_color(theme.yellow)
+                            .font_light()
+                            .text_sm(),
+                    ),
+            )
+    }
+}
+
+impl Render for BoardPicker {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.is_loading {
+            return div()
+                .track_focus(&self.focus_handle)
+                .child(Self::load_circle());
+        }
+
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| self.render_entry(entry, cx).into_any_element())
+            .collect::<Vec<_>>();
+
+        div().track_focus(&self.focus_handle).child(
+            div()
+                .id("BoardPickerList")
+                .w_full()
+                .max_h(PICKER_HEIGHT)
+                .overflow_y_scroll()
+                .children(entries),
+        )
+    }
+}
+
+impl Focusable for BoardPicker {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+pub enum BoardPickerEvent {
+    Load,
+    Pin(BoardInfo),
+    Unpin(u32),
+}
+
+impl EventEmitter<BoardPickerEvent> for BoardPicker {}
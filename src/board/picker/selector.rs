@@ -0,0 +1,33 @@
+use scraper::Selector;
+
+pub struct Selectors {
+    pub grid_items: Selector,
+    pub grid_name: Selector,
+    pub grid_today_posts: Selector,
+    pub grid_threads: Selector,
+    pub list_items: Selector,
+    pub list_name: Selector,
+    pub list_today_posts: Selector,
+    pub list_threads: Selector,
+}
+
+impl Selectors {
+    pub fn new() -> Self {
+        macro_rules! parse_selector {
+            ($s:expr) => {
+                Selector::parse($s).expect(concat!("Failed to parse selector: ", $s))
+            };
+        }
+
+        Self {
+            grid_items: parse_selector!("table.fl_tb td.fl_g"),
+            grid_name: parse_selector!("dl > dt > a"),
+            grid_today_posts: parse_selector!("dl > dt > em"),
+            grid_threads: parse_selector!("dl > dd > em"),
+            list_items: parse_selector!("table.fl_tb > tbody > tr"),
+            list_name: parse_selector!("td > h2 > a"),
+            list_today_posts: parse_selector!("td > h2 > em"),
+            list_threads: parse_selector!("td.fl_i > span.xi2"),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use std::sync::LazyLock;
+
+use smol::lock::Semaphore;
+
+const MAX_CONCURRENT: usize = 2;
+
+static LIMIT: LazyLock<Semaphore> = LazyLock::new(|| Semaphore::new(MAX_CONCURRENT));
+
+pub async fn limited<T>(future: impl Future<Output = T>) -> T {
+    let _permit = LIMIT.acquire().await;
+
+    future.await
+}
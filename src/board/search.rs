@@ -0,0 +1,205 @@
+use gpui::{
+    App, AppContext as _, Context, Entity, FocusHandle, InteractiveElement, IntoElement,
+    ParentElement as _, Render, Styled as _, Window, div,
+};
+use gpui_component::button::{Button, ButtonVariants as _};
+use gpui_component::input::{InputEvent, InputState, TextInput};
+use gpui_component::{ActiveTheme as _, ContextModal as _, Selectable as _, Sizable as _};
+
+use super::detail::DetailEvent;
+use super::summary::{SearchQuery, Source, Summary, SummaryEvent};
+use super::tabs::DetailTabs;
+use super::{BoardInfo, ViewState};
+
+pub struct Search {
+    boards: Vec<BoardInfo>,
+    fid: Option<u32>,
+    keyword_state: Entity<InputState>,
+    author_state: Entity<InputState>,
+    summary: Entity<Summary>,
+    tabs: Entity<DetailTabs>,
+    focus_handle: FocusHandle,
+}
+
+impl Search {
+    pub fn new(boards: Vec<BoardInfo>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let keyword_state = cx.new(|cx| InputState::new(window, cx).placeholder("关键词"));
+        cx.subscribe_in(&keyword_state, window, Self::on_input_event)
+            .detach();
+        let author_state = cx.new(|cx| InputState::new(window, cx).placeholder("作者"));
+        cx.subscribe_in(&author_state, window, Self::on_input_event)
+            .detach();
+        let summary = cx.new(|cx| Summary::new(Source::Search(SearchQuery::default()), window, cx));
+        cx.subscribe_in(&summary, window, |this, _, event, window, cx| match event {
+            SummaryEvent::LoadDetail(detail_url, fid) => {
+                this.tabs.update(cx, |tabs, cx| {
+                    tabs.send(DetailEvent::Load(detail_url.clone(), *fid, 1), cx)
+                });
+            }
+            SummaryEvent::LoadDetailInNewTab(detail_url, fid) => {
+                this.tabs.update(cx, |tabs, cx| {
+                    tabs.open_in_new_tab(DetailEvent::Load(detail_url.clone(), *fid, 1), window, cx)
+                });
+            }
+            _ => {}
+        })
+        .detach();
+        let tabs = cx.new(|cx| DetailTabs::new(window, cx));
+
+        Self {
+            boards,
+            fid: None,
+            keyword_state,
+            author_state,
+            summary,
+            tabs,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn tabs(&self) -> &Entity<DetailTabs> {
+        &self.tabs
+    }
+
+    pub fn view_state(&self, cx: &App) -> ViewState {
+        ViewState::capture(Some(&self.summary), &self.tabs, cx)
+    }
+
+    pub fn restore(&mut self, state: ViewState, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(Source::Search(query)) = state.list.as_ref().map(|list| list.source()) {
+            self.fid = query.fid;
+            self.keyword_state.update(cx, |input, cx| {
+                input.set_value(query.keyword.clone(), window, cx);
+            });
+            self.author_state.update(cx, |input, cx| {
+                input.set_value(query.author.clone().unwrap_or_default(), window, cx);
+            });
+        }
+        state.restore(Some(&self.summary), &self.tabs, window, cx);
+    }
+
+    pub fn set_boards(&mut self, boards: Vec<BoardInfo>, cx: &mut Context<Self>) {
+        if let Some(fid) = self.fid {
+            if !boards.iter().any(|board| board.fid == fid) {
+                self.fid = None;
+            }
+        }
+        self.boards = boards;
+        cx.notify();
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::PressEnter { secondary: _ } => self.submit(window, cx),
+            _ => {}
+        }
+    }
+
+    fn submit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let keyword = self.keyword_state.read(cx).value().trim().to_string();
+        let author = self.author_state.read(cx).value().trim().to_string();
+        if keyword.is_empty() && author.is_empty() {
+            window.push_notification("请输入关键词或作者", cx);
+            return;
+        }
+
+        let query = SearchQuery {
+            keyword,
+            fid: self.fid,
+            author: (!author.is_empty()).then_some(author),
+            search_id: None,
+        };
+        self.summary.update(cx, |_, cx| {
+            cx.emit(SummaryEvent::Search(query));
+            cx.notify();
+        });
+    }
+
+    fn render_board_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let all = Button::new("SearchAllBoards")
+            .label("全部")
+            .ghost()
+            .small()
+            .cursor_pointer()
+            .selected(self.fid.is_none())
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.fid = None;
+                cx.notify();
+            }));
+        let boards = self
+            .boards
+            .iter()
+            .map(|board| {
+                let fid = board.fid;
+                Button::new(("SearchBoard", fid as usize))
+                    .label(board.name.clone())
+                    .ghost()
+                    .small()
+                    .cursor_pointer()
+                    .selected(self.fid == Some(fid))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.fid = Some(fid);
+                        cx.notify();
+                    }))
+            })
+            .collect::<Vec<_>>();
+
+        div().flex().flex_wrap().gap_1().child(all).children(boards)
+    }
+
+    fn render_form(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let board_filter = self.render_board_filter(cx);
+        let theme = cx.theme();
+
+        div()
+            .w_full()
+            .p_2()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .border_b_1()
+            .border_r_1()
+            .border_color(theme.border)
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(div().flex_1().child(TextInput::new(&self.keyword_state)))
+                    .child(div().w_40().child(TextInput::new(&self.author_state)))
+                    .child(
+                        Button::new("SearchSubmit")
+                            .label("搜索")
+                            .cursor_pointer()
+                            .on_click(cx.listener(|this, _, window, cx| this.submit(window, cx))),
+                    ),
+            )
+            .child(board_filter)
+    }
+}
+
+impl Render for Search {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let form = self.render_form(cx);
+
+        div()
+            .track_focus(&self.focus_handle)
+            .w_full()
+            .h_full()
+            .flex()
+            .child(
+                div()
+                    .h_full()
+                    .flex()
+                    .flex_col()
+                    .child(form)
+                    .child(div().flex_1().min_h_0().child(self.summary.clone())),
+            )
+            .child(self.tabs.clone())
+    }
+}
@@ -7,9 +7,10 @@ pub struct Article {
     pub published_at: NaiveDate,
     pub view: u32,
     pub reply: u32,
-    pub last_reply: LastReply,
+    pub last_reply: Option<LastReply>,
     pub preview_images: Vec<SharedString>,
     pub href: SharedString,
+    pub fid: Option<u32>,
 }
 
 pub struct LastReply {
@@ -19,5 +20,5 @@ pub struct LastReply {
 
 pub struct Author {
     pub name: SharedString,
-    pub picture: SharedString,
+    pub picture: Option<SharedString>,
 }
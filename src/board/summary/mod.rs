@@ -0,0 +1,1246 @@
+mod article;
+mod options;
+mod selector;
+mod source;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use article::{Article, Author, LastReply};
+use chrono::{NaiveDate, NaiveDateTime};
+use gpui::{
+    AnyElement, AnyWindowHandle, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, InteractiveElement, IntoElement, ListAlignment, ListOffset, ListState,
+    MouseButton, MouseDownEvent, ParentElement as _, Pixels, Render, SharedString, Styled as _,
+    Task, WeakEntity, Window, div, img, list, prelude::FluentBuilder as _, px,
+};
+use gpui_component::button::ButtonVariants as _;
+use gpui_component::input::{InputEvent, InputState, NumberInput, NumberInputEvent, StepAction};
+use gpui_component::{
+    ActiveTheme as _, ContextModal as _, Disableable, Icon, Selectable as _, Sizable, StyledExt,
+    avatar::Avatar, button::Button, indicator::Indicator, label::Label,
+};
+use http_client::{AsyncBody, HttpClient, HttpRequestExt as _, RedirectPolicy, Request};
+use options::{Filter, ListOptions, Order, ThreadType};
+use scraper::{ElementRef, Html};
+use selector::Selectors;
+
+use super::prefetch;
+
+use crate::account::Account;
+use crate::bookmark::{Bookmark, BookmarkStore};
+use crate::history::HistoryStore;
+use crate::icon::IconName;
+use crate::image_viewer::ImageViewer;
+use crate::link;
+use crate::mirror;
+use crate::page;
+use crate::scroll;
+
+pub use source::{SearchQuery, Source};
+
+const SUMMARY_WIDTH: Pixels = px(700.);
+const PAGER_HEIGHT: Pixels = px(50.);
+const LOAD_MORE_THRESHOLD: usize = 3;
+
+pub struct Summary {
+    source: Source,
+    selectors: Arc<Selectors>,
+    articles: Vec<Article>,
+    thread_types: Vec<ThreadType>,
+    list_state: ListState,
+    page: u32,
+    page_state: Entity<InputState>,
+    page_input_value: u32,
+    is_loading: bool,
+    load_generation: usize,
+    load_task: Option<Task<()>>,
+    is_appending: bool,
+    append_task: Option<Task<()>>,
+    has_more: bool,
+    prefetched: HashMap<u32, ArticlePage>,
+    prefetch_tasks: HashMap<u32, Task<()>>,
+    load_error: Option<(u32, SharedString)>,
+    pending_scroll: Option<ListOffset>,
+    cached_at: Option<NaiveDateTime>,
+    focus_handle: FocusHandle,
+    window_handle: AnyWindowHandle,
+}
+
+impl Summary {
+    pub fn new(source: Source, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let entity = cx.entity();
+        cx.subscribe(&entity, Self::on_event).detach();
+        cx.observe(&BookmarkStore::global(cx), |_, _, cx| cx.notify())
+            .detach();
+        cx.observe(&HistoryStore::global(cx), |_, _, cx| cx.notify())
+            .detach();
+        let account = Account::global(cx);
+        let mut username = account.read(cx).username();
+        cx.observe(&account, move |this, account, cx| {
+            let current = account.read(cx).username();
+            if current != username {
+                username = current;
+                this.clear_prefetch();
+            }
+        })
+        .detach();
+        let page_state = cx.new(|cx| InputState::new(window, cx).placeholder(""));
+        cx.subscribe_in(&page_state, window, Self::on_input_event)
+            .detach();
+        cx.subscribe_in(&page_state, window, Self::on_number_input_event)
+            .detach();
+        let list_state = ListState::new(0, ListAlignment::Top, px(1000.));
+        let this = entity.downgrade();
+        list_state.set_scroll_handler(move |event, _, cx| {
+            if event.visible_range.end + LOAD_MORE_THRESHOLD < event.count {
+                return;
+            }
+
+            this.update(cx, |this, cx| this.load_more(cx)).ok();
+        });
+
+        Self {
+            source,
+            selectors: Arc::new(Selectors::new()),
+            articles: Vec::new(),
+            thread_types: Vec::new(),
+            list_state,
+            page: 0,
+            page_state,
+            page_input_value: 0,
+            is_loading: false,
+            load_generation: 0,
+            load_task: None,
+            is_appending: false,
+            append_task: None,
+            has_more: true,
+            prefetched: HashMap::new(),
+            prefetch_tasks: HashMap::new(),
+            load_error: None,
+            pending_scroll: None,
+            cached_at: None,
+            focus_handle: cx.focus_handle(),
+            window_handle: window.window_handle(),
+        }
+    }
+
+    fn on_event(&mut self, _: Entity<Self>, evt: &SummaryEvent, cx: &mut Context<Self>) {
+        match evt {
+            SummaryEvent::Load(page) => {
+                self.pending_scroll = None;
+                self.event_load(*page, cx);
+            }
+            SummaryEvent::Search(query) => {
+                self.pending_scroll = None;
+                self.event_search(query, cx);
+            }
+            SummaryEvent::Restore(location) => self.event_restore(location, cx),
+            _ => {}
+        }
+    }
+
+    fn event_search(&mut self, query: &SearchQuery, cx: &mut Context<Self>) {
+        self.clear_prefetch();
+        self.source = Source::Search(query.clone());
+        self.event_load(1, cx);
+    }
+
+    pub fn location(&self) -> Option<ListLocation> {
+        (self.page > 0).then(|| ListLocation {
+            source: self.source.clone(),
+            page: self.page,
+            scroll_top: self.list_state.logical_scroll_top(),
+        })
+    }
+
+    fn event_restore(&mut self, location: &ListLocation, cx: &mut Context<Self>) {
+        if self.source != location.source {
+            self.clear_prefetch();
+            self.source = location.source.clone();
+        } else if !self.is_loading && self.page == location.page {
+            self.list_state.scroll_to(location.scroll_top);
+            cx.notify();
+            return;
+        }
+
+        self.pending_scroll = Some(location.scroll_top);
+        self.event_load(location.page, cx);
+    }
+
+    fn event_load(&mut self, page: u32, cx: &mut Context<Self>) {
+        self.load_generation += 1;
+        let generation = self.load_generation;
+        self.append_task = None;
+        self.is_appending = false;
+        if let Some(article_page) = self.prefetched.remove(&page) {
+            self.load_task = None;
+            self.load_error = None;
+            self.load_success(article_page, page, cx);
+            return;
+        }
+        let pending = self.prefetch_tasks.remove(&page);
+        self.is_loading = true;
+        self.load_error = None;
+        cx.notify();
+
+        let client = cx.http_client();
+        let selectors = self.selectors.clone();
+        let source = self.source.clone();
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            let articles = match Self::take_prefetched(&this, pending, page, cx).await {
+                Some(article_page) => Ok(article_page),
+                None => {
+                    cx.background_spawn(async move {
+                        Self::load_page(client, &selectors, &source, page).await
+                    })
+                    .await
+                }
+            };
+            if let Some(this) = this.upgrade() {
+                this.update(cx, |this, cx| {
+                    if this.load_generation != generation {
+                        return;
+                    }
+
+                    match articles {
+                        Ok(articles) => this.load_success(articles, page, cx),
+                        Err(error) => this.load_failure(error, page, cx),
+                    }
+                })
+                .ok();
+            }
+        }));
+    }
+
+    fn load_failure(&mut self, error: anyhow::Error, page: u32, cx: &mut Context<Self>) {
+        let message = SharedString::from(error.to_string());
+        cx.update_window(self.window_handle, |_, window, cx| {
+            window.push_notification(message.clone(), cx);
+        })
+        .ok();
+        self.load_error = Some((page, message));
+        self.pending_scroll = None;
+        self.is_loading = false;
+        cx.notify();
+    }
+
+    fn load_success(&mut self, article_page: ArticlePage, page: u32, cx: &mut Context<Self>) {
+        if let (Source::Search(query), Some(search_id)) = (&mut self.source, article_page.search_id)
+        {
+            query.search_id = Some(search_id);
+        }
+        self.page = page;
+        self.page_input_value = page;
+        self.cached_at = article_page.cached_at;
+        cx.update_window(self.window_handle, |_, window, cx| {
+            self.page_state.update(cx, |this, cx| {
+                this.set_value(self.page_input_value.to_string(), window, cx);
+            });
+        })
+        .ok();
+        if !article_page.thread_types.is_empty() {
+            self.thread_types = article_page.thread_types;
+        }
+        self.articles.clear();
+        self.articles.extend(article_page.articles);
+        self.list_state.reset(self.articles.len() + 1);
+        if let Some(scroll_top) = self.pending_scroll.take() {
+            self.list_state.scroll_to(ListOffset {
+                item_ix: scroll_top.item_ix.min(self.articles.len()),
+                ..scroll_top
+            });
+        }
+        self.has_more = !self.articles.is_empty();
+        self.is_loading = false;
+        self.prefetch_around(page, cx);
+        cx.notify();
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if !scroll::is_infinite()
+            || !self.has_more
+            || self.is_loading
+            || self.is_appending
+            || self.load_error.is_some()
+        {
+            return;
+        }
+
+        let page = self.page + 1;
+        if let Some(article_page) = self.prefetched.remove(&page) {
+            self.append_success(article_page, page, cx);
+            return;
+        }
+        let pending = self.prefetch_tasks.remove(&page);
+        self.is_appending = true;
+        cx.notify();
+
+        let generation = self.load_generation;
+        let client = cx.http_client();
+        let selectors = self.selectors.clone();
+        let source = self.source.clone();
+        self.append_task = Some(cx.spawn(async move |this, cx| {
+            let articles = match Self::take_prefetched(&this, pending, page, cx).await {
+                Some(article_page) => Ok(article_page),
+                None => {
+                    cx.background_spawn(async move {
+                        Self::load_page(client, &selectors, &source, page).await
+                    })
+                    .await
+                }
+            };
+            if let Some(this) = this.upgrade() {
+                this.update(cx, |this, cx| {
+                    if this.load_generation != generation {
+                        return;
+                    }
+
+                    match articles {
+                        Ok(articles) => this.append_success(articles, page, cx),
+                        Err(error) => this.append_failure(error, cx),
+                    }
+                })
+                .ok();
+            }
+        }));
+    }
+
+    fn append_failure(&mut self, error: anyhow::Error, cx: &mut Context<Self>) {
+        cx.update_window(self.window_handle, |_, window, cx| {
+            window.push_notification(error.to_string(), cx);
+        })
+        .ok();
+        self.is_appending = false;
+        cx.notify();
+    }
+
+    fn append_success(&mut self, article_page: ArticlePage, page: u32, cx: &mut Context<Self>) {
+        let shown = self
+            .articles
+            .iter()
+            .map(|article| article.href.clone())
+            .collect::<HashSet<_>>();
+        let articles = article_page
+            .articles
+            .into_iter()
+            .filter(|article| !shown.contains(&article.href))
+            .collect::<Vec<_>>();
+        self.page = page;
+        self.page_input_value = page;
+        cx.update_window(self.window_handle, |_, window, cx| {
+            self.page_state.update(cx, |this, cx| {
+                this.set_value(self.page_input_value.to_string(), window, cx);
+            });
+        })
+        .ok();
+        let start = self.articles.len();
+        self.list_state.splice(start..start, articles.len());
+        self.has_more = !articles.is_empty();
+        self.articles.extend(articles);
+        self.is_appending = false;
+        self.prefetch_around(page, cx);
+        cx.notify();
+    }
+
+    fn prefetch_around(&mut self, page: u32, cx: &mut Context<Self>) {
+        self.prefetched
+            .retain(|cached, _| cached.abs_diff(page) == 1);
+        self.prefetch_tasks
+            .retain(|pending, _| pending.abs_diff(page) == 1);
+        if self.has_more {
+            self.prefetch(page + 1, cx);
+        }
+    }
+
+    fn prefetch(&mut self, page: u32, cx: &mut Context<Self>) {
+        if self.prefetched.contains_key(&page) || self.prefetch_tasks.contains_key(&page) {
+            return;
+        }
+
+        let client = cx.http_client();
+        let selectors = self.selectors.clone();
+        let source = self.source.clone();
+        let task = cx.background_spawn(prefetch::limited(async move {
+            Self::load_page(client, &selectors, &source, page).await
+        }));
+        let task = cx.spawn(async move |this, cx| {
+            let article_page = task.await;
+            this.update(cx, |this, _| {
+                this.prefetch_tasks.remove(&page);
+                if let Ok(article_page) = article_page {
+                    this.prefetched.insert(page, article_page);
+                }
+            })
+            .ok();
+        });
+        self.prefetch_tasks.insert(page, task);
+    }
+
+    async fn take_prefetched(
+        this: &WeakEntity<Self>,
+        pending: Option<Task<()>>,
+        page: u32,
+        cx: &mut AsyncApp,
+    ) -> Option<ArticlePage> {
+        pending?.await;
+
+        this.update(cx, |this, _| this.prefetched.remove(&page))
+            .ok()
+            .flatten()
+    }
+
+    fn clear_prefetch(&mut self) {
+        self.prefetched.clear();
+        self.prefetch_tasks.clear();
+    }
+
+    async fn load_page(
+        http_client: Arc<dyn HttpClient>,
+        selectors: &Selectors,
+        source: &Source,
+        page: u32,
+    ) -> anyhow::Result<ArticlePage> {
+        let url = source.url(page);
+        let (text, cached_at) = page::fetch(&http_client, |cookie| {
+            Request::builder()
+                .method("GET")
+                .uri(&url)
+                .follow_redirects(RedirectPolicy::FollowAll)
+                .header("Cookie", cookie)
+                .header("Accept-Language", "zh-CN,zh-Hans;q=0.9")
+                .body(AsyncBody::empty())
+                .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))
+        })
+        .await?;
+
+        let mut article_page = match source {
+            Source::Board(..) => Self::parse_page(&text, selectors),
+            Source::Search(_) => Self::parse_search_page(&text, selectors),
+        };
+        article_page.cached_at = cached_at;
+
+        Ok(article_page)
+    }
+
+    fn parse_page(text: &str, selectors: &Selectors) -> ArticlePage {
+        let html = Html::parse_document(&text);
+        let articles = html
+            .select(&selectors.items)
+            .into_iter()
+            .flat_map(|item| Self::parse_single_article(item, selectors))
+            .collect();
+        let thread_types = html
+            .select(&selectors.thread_types)
+            .flat_map(|a| {
+                let id = a
+                    .attr("href")?
+                    .split(&['?', '&'])
+                    .find_map(|pair| pair.strip_prefix("typeid="))
+                    .and_then(|type_id| type_id.parse::<u32>().ok())?;
+                let name = a.text().collect::<String>().trim().to_string();
+
+                Some(ThreadType {
+                    id,
+                    name: SharedString::from(name),
+                })
+            })
+            .collect();
+
+        ArticlePage {
+            articles,
+            thread_types,
+            search_id: None,
+            cached_at: None,
+        }
+    }
+
+    fn parse_single_article(item: ElementRef, selectors: &Selectors) -> Option<Article> {
+        let title = item
+            .select(&selectors.title)
+            .next()
+            .map(|title| title.text())
+            .map(|title| title.collect::<String>())
+            .map(SharedString::from)?;
+        let author_picture = item
+            .select(&selectors.author_picture)
+            .next()
+            .and_then(|img| img.attr("src"))
+            .map(String::from)
+            .map(SharedString::from);
+        let author_name = item
+            .select(&selectors.author_name)
+            .next()
+            .map(|name| name.text())
+            .map(|name| name.collect::<String>())
+            .map(SharedString::from)?;
+        let author = Author {
+            name: author_name,
+            picture: author_picture,
+        };
+        let published_at = item
+            .select(&selectors.published_at)
+            .next()
+            .and_then(|span| span.attr("title"))
+            .and_then(|time| NaiveDate::parse_from_str(time, "%Y-%m-%d").ok())
+            .or_else(|| {
+                item.select(&selectors.published_at_normal)
+                    .next()
+                    .map(|span| span.text().collect::<String>())
+                    .and_then(|time| NaiveDate::parse_from_str(&time, "%Y-%m-%d").ok())
+            })?;
+        let view = item
+            .select(&selectors.view)
+            .next()
+            .map(|view| view.text())
+            .map(|view| view.collect::<String>())
+            .and_then(|view| view.parse::<u32>().ok())?;
+        let reply = item
+            .select(&selectors.reply)
+            .next()
+            .map(|reply| reply.text())
+            .map(|reply| reply.collect::<String>())
+            .and_then(|reply| reply.parse::<u32>().ok())?;
+        let last_reply_name = item
+            .select(&selectors.last_reply_name)
+            .next()
+            .map(|name| name.text())
+            .map(|name| name.collect::<String>())
+            .map(SharedString::from)?;
+        let last_reply_published_at = item
+            .select(&selectors.last_reply_published_at)
+            .next()
+            .and_then(|span| span.attr("title"))
+            .and_then(|time| NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M").ok())
+            .or_else(|| {
+                item.select(&selectors.last_reply_published_at_normal)
+                    .next()
+                    .map(|span| span.text().collect::<String>())
+                    .and_then(|time| NaiveDateTime::parse_from_str(&time, "%Y-%m-%d %H:%M").ok())
+            })?;
+        let last_reply = LastReply {
+            name: last_reply_name,
+            published_at: last_reply_published_at,
+        };
+        let preview_images = item
+            .select(&selectors.preview_images)
+            .into_iter()
+            .flat_map(|img| {
+                img.attr("src").and_then(|src| match src {
+                    "template/javbus/images/folder_lock.gif"
+                    | "template/javbus/images/pollsmall.gif" => None,
+                    _ => Some(src),
+                })
+            })
+            .map(mirror::forum_url)
+            .map(SharedString::from)
+            .collect();
+        let href = item
+            .select(&selectors.href)
+            .next()
+            .and_then(|href| href.attr("href"))
+            .map(link::thread_href)
+            .map(SharedString::from)?;
+
+        let article = Article {
+            title,
+            author,
+            published_at,
+            view,
+            reply,
+            last_reply: Some(last_reply),
+            preview_images,
+            href,
+            fid: None,
+        };
+
+        Some(article)
+    }
+
+    fn parse_search_page(text: &str, selectors: &Selectors) -> ArticlePage {
+        let html = Html::parse_document(text);
+        let articles = html
+            .select(&selectors.search_items)
+            .into_iter()
+            .flat_map(|item| Self::parse_search_article(item, selectors))
+            .collect();
+        let search_id = html
+            .select(&selectors.search_pages)
+            .flat_map(|a| a.attr("href"))
+            .find_map(|href| {
+                href.split(&['?', '&'])
+                    .find_map(|pair| pair.strip_prefix("searchid="))
+                    .and_then(|search_id| search_id.parse::<u32>().ok())
+            });
+
+        ArticlePage {
+            articles,
+            thread_types: Vec::new(),
+            search_id,
+            cached_at: None,
+        }
+    }
+
+    fn parse_search_article(item: ElementRef, selectors: &Selectors) -> Option<Article> {
+        let title = item
+            .select(&selectors.search_title)
+            .next()
+            .map(|title| title.text())
+            .map(|title| title.collect::<String>())
+            .map(SharedString::from)?;
+        let href = item
+            .select(&selectors.search_title)
+            .next()
+            .and_then(|href| href.attr("href"))
+            .map(link::thread_href)
+            .map(SharedString::from)?;
+        let author_name = item
+            .select(&selectors.search_author_name)
+            .next()
+            .map(|name| name.text())
+            .map(|name| name.collect::<String>())
+            .map(SharedString::from)?;
+        let author = Author {
+            name: author_name,
+            picture: None,
+        };
+        let published_at = item
+            .select(&selectors.search_published_at)
+            .next()
+            .map(|span| span.text().collect::<String>())
+            .and_then(|time| {
+                NaiveDateTime::parse_from_str(time.trim(), "%Y-%m-%d %H:%M")
+                    .map(|time| time.date())
+                    .or_else(|_| NaiveDate::parse_from_str(time.trim(), "%Y-%m-%d"))
+                    .ok()
+            })?;
+        let stats = item
+            .select(&selectors.search_stats)
+            .next()
+            .map(|p| p.text().collect::<String>())?;
+        let mut numbers = stats
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|number| !number.is_empty())
+            .flat_map(|number| number.parse::<u32>().ok());
+        let reply = numbers.next().unwrap_or(0);
+        let view = numbers.next().unwrap_or(0);
+        let fid = item
+            .select(&selectors.search_board)
+            .next()
+            .and_then(|a| a.attr("href"))
+            .and_then(|href| {
+                href.split(&['?', '&'])
+                    .find_map(|pair| pair.strip_prefix("fid="))
+                    .and_then(|fid| fid.parse::<u32>().ok())
+            });
+
+        let article = Article {
+            title,
+            author,
+            published_at,
+            view,
+            reply,
+            last_reply: None,
+            preview_images: Vec::new(),
+            href,
+            fid,
+        };
+
+        Some(article)
+    }
+
+    fn set_options(&mut self, options: ListOptions, cx: &mut Context<Self>) {
+        let Source::Board(fid, ref mut current) = self.source else {
+            return;
+        };
+        if *current == options {
+            return;
+        }
+
+        *current = options;
+        self.clear_prefetch();
+        if let Err(error) = options.save(fid) {
+            cx.update_window(self.window_handle, |_, window, cx| {
+                window.push_notification(error.to_string(), cx);
+            })
+            .ok();
+        }
+        cx.emit(SummaryEvent::Load(1));
+        cx.notify();
+    }
+
+    fn on_item_click(
+        detail_url: SharedString,
+        fid: Option<u32>,
+        reply: u32,
+        last_reply_at: Option<NaiveDateTime>,
+        new_tab: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let history_store = HistoryStore::global(cx);
+        history_store.update(cx, |history_store, cx| {
+            history_store.record(&detail_url, reply, last_reply_at, cx)
+        });
+        if new_tab {
+            cx.emit(SummaryEvent::LoadDetailInNewTab(detail_url, fid));
+        } else {
+            cx.emit(SummaryEvent::LoadDetail(detail_url, fid));
+        }
+        cx.notify();
+    }
+
+    fn toggle_bookmark(bookmark: Bookmark, window: &mut Window, cx: &mut Context<Self>) {
+        let bookmark_store = BookmarkStore::global(cx);
+        let result =
+            bookmark_store.update(cx, |bookmark_store, cx| bookmark_store.toggle(bookmark, cx));
+        if let Err(error) = result {
+            window.push_notification(error.to_string(), cx);
+        }
+    }
+
+    fn render_bookmark_button(
+        &self,
+        article: &Article,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let is_bookmarked = BookmarkStore::global(cx).read(cx).contains(&article.href);
+        let bookmark = Bookmark::new(
+            article.title.clone(),
+            article.href.clone(),
+            article.fid.or(self.source.fid()),
+            article.author.name.clone(),
+        );
+
+        div()
+            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+            .child(
+                Button::new(article.href.clone())
+                    .icon(if is_bookmarked {
+                        IconName::BookmarkCheck
+                    } else {
+                        IconName::Bookmark
+                    })
+                    .ghost()
+                    .small()
+                    .cursor_pointer()
+                    .on_click(cx.listener(move |_, _, window, cx| {
+                        Self::toggle_bookmark(bookmark.clone(), window, cx)
+                    })),
+            )
+    }
+
+    fn render_article(&self, article: &Article, cx: &mut Context<Self>) -> impl IntoElement {
+        let href = article.href.clone();
+        let middle_href = article.href.clone();
+        let reply = article.reply;
+        let fid = article.fid.or(self.source.fid());
+        let last_reply_at = article
+            .last_reply
+            .as_ref()
+            .map(|last_reply| last_reply.published_at);
+        let visit = HistoryStore::global(cx)
+            .read(cx)
+            .visit(&article.href)
+            .cloned();
+        let new_replies = visit
+            .as_ref()
+            .map(|visit| article.reply.saturating_sub(visit.reply))
+            .unwrap_or(0);
+        let is_read = visit.is_some() && new_replies == 0;
+        let bookmark_button = self.render_bookmark_button(article, cx);
+        let theme = cx.theme();
+
+        div()
+            .p_2()
+            .rounded_md()
+            .bg(theme.secondary_hover)
+            .border_1()
+            .border_color(theme.border)
+            .hover(|style| style.bg(theme.secondary_active))
+            .cursor_pointer()
+            .when(is_read, |this| this.opacity(0.6))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |_, event: &MouseDownEvent, _, cx| {
+                    let new_tab = event.modifiers.secondary();
+                    Self::on_item_click(href.clone(), fid, reply, last_reply_at, new_tab, cx)
+                }),
+            )
+            .on_mouse_down(
+                MouseButton::Middle,
+                cx.listener(move |_, _, _, cx| {
+                    Self::on_item_click(middle_href.clone(), fid, reply, last_reply_at, true, cx)
+                }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_start()
+                    .justify_between()
+                    .gap_1()
+                    .child(Label::new(article.title.clone()).font_semibold().text_lg())
+                    .child(bookmark_button),
+            )
+            .child(
+                div()
+                    .flex()
+                    .pt_1()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .child(
+                                Avatar::new()
+                                    .xsmall()
+                                    .name(article.author.name.clone())
+                                    .when_some(
+                                        article.author.picture.clone(),
+                                        |avatar, picture| avatar.src(picture),
+                                    ),
+                            )
+                            .child(
+                                Label::new(article.author.name.clone())
+                                    .text_color(theme.blue)
+                                    .font_light()
+                                    .text_sm(),
+                            )
+                            .child(
+                                Label::new(article.published_at.format("@ %Y-%m-%d").to_string())
+                                    .text_color(theme.yellow)
+                                    .font_light()
+                                    .text_sm(),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .gap_1()
+                            .child(Icon::new(IconName::Eye).small())
+                            .child(
+                                Label::new(article.view.to_string())
+                                    .text_color(theme.primary_hover)
+                                    .font_light()
+                                    .text_sm(),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .gap_1()
+                            .child(Icon::new(IconName::MessageCircle).small())
+                            .child(
+                                Label::new(article.reply.to_string())
+                                    .text_color(theme.primary_hover)
+                                    .font_light()
+                                    .text_sm(),
+                            )
+                            .when(new_replies > 0, |this| {
+                                this.child(
+                                    Label::new(format!("+{new_replies} 新回复"))
+                                        .text_color(theme.red)
+                                        .text_sm(),
+                                )
+                            }),
+                    ),
+            )
+            .when_some(article.last_reply.as_ref(), |this, last_reply| {
+                this.child(
+                    div()
+                        .flex()
+                        .py_1()
+                        .gap_1()
+                        .items_center()
+                        .justify_end()
+                        .child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .gap_1()
+                                .child(Icon::new(IconName::Reply).small())
+                                .child(
+                                    Label::new(last_reply.name.clone())
+                                        .text_color(theme.blue)
+                                        .font_light()
+                                        .text_sm(),
+                                ),
+                        )
+                        .child(
+                            Label::new(
+                                last_reply
+                                    .published_at
+                                    .format("@ %Y-%m-%d %H:%M")
+                                    .to_string(),
+                            )
+                            .text_color(theme.yellow)
+                            .font_light()
+                            .text_sm(),
+                        ),
+                )
+            })
+            .when(!article.preview_images.is_empty(), |this| {
+                this.child(
+                    div()
+                        .pt_1()
+                        .h(px(100.))
+                        .w_full()
+                        .flex()
+                        .justify_between()
+                        .items_center()
+                        .children(article.preview_images.iter().enumerate().map(
+                            |(idx, preview)| {
+                                let images = article.preview_images.clone();
+                                div()
+                                    .cursor_pointer()
+                                    .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                                        cx.stop_propagation();
+                                        ImageViewer::open(images.clone(), idx, window, cx);
+                                    })
+                                    .child(
+                                        img(preview.clone())
+                                            .w(px(120.))
+                                            .rounded_md()
+                                            .bg(theme.background),
+                                    )
+                            },
+                        )),
+                )
+            })
+    }
+
+    fn render_item(&self, idx: usize, cx: &mut Context<Self>) -> AnyElement {
+        let item = if idx == self.articles.len() {
+            let theme = cx.theme();
+            div()
+                .flex()
+                .flex_col()
+                .items_center()
+                .map(|this| {
+                    if scroll::is_infinite() {
+                        this.child(self.render_more(cx))
+                    } else {
+                        this.child(self.render_pager(cx))
+                    }
+                })
+                .when_some(self.cached_at, |this, cached_at| {
+                    this.child(
+                        Label::new(cached_at.format("缓存于 %m-%d %H:%M").to_string())
+                            .text_color(theme.yellow)
+                            .font_light()
+                            .text_sm(),
+                    )
+                })
+                .into_any_element()
+        } else {
+            let article = &self.articles[idx];
+            self.render_article(article, cx).into_any_element()
+        };
+
+        div()
+            .pt_2()
+            .px_2()
+            .when(idx == self.articles.len(), |div| div.pb_2())
+            .child(item)
+            .into_any_element()
+    }
+
+    fn load_circle() -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .justify_center()
+            .items_center()
+            .child(Indicator::new().large().icon(IconName::LoaderCircle))
+    }
+
+    fn render_more(&self, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+
+        div()
+            .w_full()
+            .h(PAGER_HEIGHT)
+            .flex()
+            .justify_center()
+            .items_center()
+            .when(self.is_appending, |this| {
+                this.child(Indicator::new().small().icon(IconName::LoaderCircle))
+            })
+            .when(!self.is_appending && !self.has_more, |this| {
+                this.child(
+                    Label::new("没有更多了")
+                        .text_color(theme.muted_foreground)
+                        .text_sm(),
+                )
+            })
+    }
+
+    fn render_retry(page: u32, message: SharedString, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .justify_center()
+            .items_center()
+            .gap_2()
+            .child(Label::new(message).text_sm())
+            .child(
+                Button::new("SummaryRetry")
+                    .label("重试")
+                    .small()
+                    .cursor_pointer()
+                    .on_click(cx.listener(move |_, _, _, cx| {
+                        cx.emit(SummaryEvent::Load(page));
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn render_content(&self, _: &Window, cx: &Context<Self>) -> impl IntoElement {
+        list(
+            self.list_state.clone(),
+            cx.processor(|this, idx, _, cx| this.render_item(idx, cx)),
+        )
+        .size_full()
+    }
+
+    fn render_toolbar(&self, options: ListOptions, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let orders = Order::all().iter().map(|order| {
+            let order = *order;
+            Button::new(order.label())
+                .label(order.label())
+                .ghost()
+                .small()
+                .cursor_pointer()
+                .selected(options.order == order)
+                .disabled(options.filter == Filter::Hot)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.set_options(ListOptions { order, ..options }, cx)
+                }))
+        });
+        let filters = [
+            (Filter::All, SharedString::from("全部")),
+            (Filter::Digest, SharedString::from("精华")),
+            (Filter::Hot, SharedString::from("热门")),
+        ]
+        .into_iter()
+        .chain(
+            self.thread_types
+                .iter()
+                .map(|thread_type| (Filter::Type(thread_type.id), thread_type.name.clone())),
+        )
+        .enumerate()
+        .map(|(idx, (filter, label))| {
+            Button::new(("ForumFilter", idx))
+                .label(label)
+                .ghost()
+                .small()
+                .cursor_pointer()
+                .selected(options.filter == filter)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.set_options(ListOptions { filter, ..options }, cx)
+                }))
+        });
+
+        div()
+            .w_full()
+            .p_2()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .border_b_1()
+            .border_color(theme.border)
+            .child(div().flex().gap_1().children(orders))
+            .child(div().flex().flex_wrap().gap_1().children(filters))
+    }
+
+    fn render_pager(&self, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .w_full()
+            .h(PAGER_HEIGHT)
+            .flex()
+            .items_center()
+            .justify_between()
+            .p_2()
+            .child(
+                Button::new("ForumPrevious")
+                    .icon(IconName::ChevronLeft)
+                    .ghost()
+                    .disabled(self.page <= 1)
+                    .when(self.page > 1, |div| div.cursor_pointer())
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        let page = this.page - 1;
+                        cx.emit(SummaryEvent::Load(page));
+                        cx.notify();
+                    })),
+            )
+            .child(
+                div()
+                    .h_full()
+                    .w(px(200.))
+                    .flex()
+                    .justify_center()
+                    .items_center()
+                    .gap_1()
+                    .child(Label::new("第"))
+                    .child(NumberInput::new(&self.page_state))
+                    .child(Label::new("页")),
+            )
+            .child(
+                Button::new("ForumNext")
+                    .icon(IconName::ChevronRight)
+                    .ghost()
+                    .cursor_pointer()
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        let page = this.page + 1;
+                        cx.emit(SummaryEvent::Load(page));
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn on_input_event(
+        &mut self,
+        this: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::PressEnter { secondary: _ } => {
+                let page = self.page_input_value;
+                cx.emit(SummaryEvent::Load(page));
+                cx.focus_self(window);
+                cx.notify();
+            }
+            InputEvent::Change(text) => {
+                if this == &self.page_state {
+                    if let Ok(page) = text.parse::<u32>() {
+                        if page != 0 {
+                            self.page_input_value = page;
+                            return;
+                        }
+
+                        self.page_input_value = 1;
+                    };
+
+                    this.update(cx, |input, cx| {
+                        input.set_value(self.page_input_value.to_string(), window, cx);
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_number_input_event(
+        &mut self,
+        this: &Entity<InputState>,
+        event: &NumberInputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            NumberInputEvent::Step(step_action) => match step_action {
+                StepAction::Decrement => {
+                    if this == &self.page_state {
+                        if self.page_input_value <= 1 {
+                            return;
+                        }
+
+                        self.page_input_value = self.page_input_value - 1;
+                        this.update(cx, |input, cx| {
+                            input.set_value(self.page_input_value.to_string(), window, cx);
+                        });
+                    }
+                }
+                StepAction::Increment => {
+                    if this == &self.page_state {
+                        if self.page_input_value == std::u32::MAX {
+                            return;
+                        }
+
+                        self.page_input_value = self.page_input_value + 1;
+                        this.update(cx, |input, cx| {
+                            input.set_value(self.page_input_value.to_string(), window, cx);
+                        });
+                    }
+                }
+            },
+        }
+    }
+}
+
+impl Render for Summary {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match self.load_error.clone() {
+            Some((page, message)) => Self::render_retry(page, message, cx).into_any_element(),
+            None => self.render_content(window, cx).into_any_element(),
+        };
+        let toolbar = match self.source {
+            Source::Board(_, options) => Some(self.render_toolbar(options, cx)),
+            Source::Search(_) => None,
+        };
+        let theme = cx.theme();
+
+        div()
+            .track_focus(&self.focus_handle)
+            .h_full()
+            .w(SUMMARY_WIDTH)
+            .flex()
+            .flex_col()
+            .border_r_1()
+            .border_color(theme.border)
+            .children(toolbar)
+            .child(
+                div()
+                    .flex_1()
+                    .min_h_0()
+                    .when(self.is_loading, |div| div.child(Self::load_circle()))
+                    .when(!self.is_loading, |div| div.child(content)),
+            )
+    }
+}
+
+impl Focusable for Summary {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+pub enum SummaryEvent {
+    Load(u32),
+    Search(SearchQuery),
+    Restore(ListLocation),
+    LoadDetail(SharedString, Option<u32>),
+    LoadDetailInNewTab(SharedString, Option<u32>),
+}
+
+#[derive(Clone)]
+pub struct ListLocation {
+    source: Source,
+    page: u32,
+    scroll_top: ListOffset,
+}
+
+impl ListLocation {
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+}
+
+struct ArticlePage {
+    articles: Vec<Article>,
+    thread_types: Vec<ThreadType>,
+    search_id: Option<u32>,
+    cached_at: Option<NaiveDateTime>,
+}
+
+impl EventEmitter<SummaryEvent> for Summary {}
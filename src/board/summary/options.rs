@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use gpui::SharedString;
+use serde::{Deserialize, Serialize};
+
+use crate::store;
+
+const BOARD_OPTIONS: &str = "board_options";
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListOptions {
+    pub order: Order,
+    pub filter: Filter,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Order {
+    #[default]
+    LastPost,
+    Dateline,
+    Views,
+    Replies,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Filter {
+    #[default]
+    All,
+    Digest,
+    Hot,
+    Type(u32),
+}
+
+pub struct ThreadType {
+    pub id: u32,
+    pub name: SharedString,
+}
+
+impl ListOptions {
+    pub fn load(fid: u32) -> Self {
+        store::load::<HashMap<u32, Self>>(BOARD_OPTIONS)
+            .and_then(|options| options.get(&fid).copied())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, fid: u32) -> anyhow::Result<()> {
+        let mut options = store::load::<HashMap<u32, Self>>(BOARD_OPTIONS).unwrap_or_default();
+        options.insert(fid, *self);
+
+        store::save(BOARD_OPTIONS, &options)
+    }
+
+    pub fn query(&self) -> String {
+        let order = self.order.query();
+        match self.filter {
+            Filter::All => format!("&filter=lastpost&orderby={order}"),
+            Filter::Digest => format!("&filter=digest&digest=1&orderby={order}"),
+            Filter::Hot => "&filter=heat&orderby=heats".to_string(),
+            Filter::Type(type_id) => format!("&filter=typeid&typeid={type_id}&orderby={order}"),
+        }
+    }
+}
+
+impl Order {
+    pub fn all() -> &'static [Self] {
+        &[Self::LastPost, Self::Dateline, Self::Views, Self::Replies]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Order::LastPost => "最后回复",
+            Order::Dateline => "发帖时间",
+            Order::Views => "查看",
+            Order::Replies => "回复",
+        }
+    }
+
+    fn query(&self) -> &'static str {
+        match self {
+            Order::LastPost => "lastpost",
+            Order::Dateline => "dateline",
+            Order::Views => "views",
+            Order::Replies => "replies",
+        }
+    }
+}
@@ -14,6 +14,14 @@ pub struct Selectors {
     pub last_reply_published_at: Selector,
     pub last_reply_published_at_normal: Selector,
     pub href: Selector,
+    pub thread_types: Selector,
+    pub search_items: Selector,
+    pub search_title: Selector,
+    pub search_stats: Selector,
+    pub search_published_at: Selector,
+    pub search_author_name: Selector,
+    pub search_board: Selector,
+    pub search_pages: Selector,
 }
 
 impl Selectors {
@@ -56,6 +64,14 @@ impl Selectors {
                 "tr > th > div.post_inforight > div.post_infolist_other > span > span:nth-child(3)"
             ),
             href: parse_selector!("tr > th > div.post_inforight > div.post_infolist > div > a.s"),
+            thread_types: parse_selector!("#thread_types > li > a"),
+            search_items: parse_selector!("#threadlist > ul > li.pbw"),
+            search_title: parse_selector!("h3.xs3 > a"),
+            search_stats: parse_selector!("p.xg1"),
+            search_published_at: parse_selector!("p:last-child > span:nth-child(1)"),
+            search_author_name: parse_selector!("p:last-child > span:nth-child(2) > a"),
+            search_board: parse_selector!("p:last-child > span:nth-child(3) > a"),
+            search_pages: parse_selector!("div.pg > a"),
         }
     }
 }
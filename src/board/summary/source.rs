@@ -0,0 +1,63 @@
+use super::options::ListOptions;
+use crate::mirror;
+use crate::page::encode;
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum Source {
+    Board(u32, ListOptions),
+    Search(SearchQuery),
+}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub keyword: String,
+    pub fid: Option<u32>,
+    pub author: Option<String>,
+    pub search_id: Option<u32>,
+}
+
+impl Source {
+    pub fn board(fid: u32) -> Self {
+        Self::Board(fid, ListOptions::load(fid))
+    }
+
+    pub fn fid(&self) -> Option<u32> {
+        match self {
+            Source::Board(fid, _) => Some(*fid),
+            Source::Search(query) => query.fid,
+        }
+    }
+
+    pub fn url(&self, page: u32) -> String {
+        match self {
+            Source::Board(fid, options) => mirror::forum_url(&format!(
+                "forum.php?mod=forumdisplay&fid={fid}{}&page={page}",
+                options.query()
+            )),
+            Source::Search(query) => query.url(page),
+        }
+    }
+}
+
+impl SearchQuery {
+    fn url(&self, page: u32) -> String {
+        if let Some(search_id) = self.search_id {
+            return mirror::forum_url(&format!(
+                "search.php?mod=forum&searchid={search_id}&orderby=lastpost&ascdesc=desc&searchsubmit=yes&page={page}"
+            ));
+        }
+
+        let mut url = mirror::forum_url(&format!(
+            "search.php?mod=forum&searchsubmit=yes&srchtxt={}",
+            encode(&self.keyword)
+        ));
+        if let Some(fid) = self.fid {
+            url.push_str(&format!("&srchfid%5B%5D={fid}"));
+        }
+        if let Some(ref author) = self.author {
+            url.push_str(&format!("&srchuname={}", encode(author)));
+        }
+
+        url
+    }
+}
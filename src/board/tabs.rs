@@ -0,0 +1,335 @@
+use gpui::{
+    App, AppContext as _, Context, Entity, EntityId, EventEmitter, FocusHandle, InteractiveElement,
+    IntoElement, MouseButton, ParentElement as _, Pixels, Render, SharedString,
+    StatefulInteractiveElement as _, Styled as _, Window, div, prelude::FluentBuilder as _, px,
+};
+use gpui_component::button::{Button, ButtonVariants as _};
+use gpui_component::{ActiveTheme as _, Disableable as _, Sizable as _};
+
+use super::detail::{Detail, DetailEvent, ThreadLocation};
+use crate::icon::IconName;
+
+const TAB_WIDTH: Pixels = px(180.);
+const MAX_CLOSED: usize = 20;
+
+pub struct DetailTabs {
+    tabs: Vec<Entity<Detail>>,
+    active: usize,
+    closed: Vec<ThreadLocation>,
+    focus_handle: FocusHandle,
+}
+
+impl DetailTabs {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            tabs: Vec::new(),
+            active: 0,
+            closed: Vec::new(),
+            focus_handle: cx.focus_handle(),
+        };
+        let detail = this.new_detail(window, cx);
+        this.tabs.push(detail);
+
+        this
+    }
+
+    pub fn active(&self) -> &Entity<Detail> {
+        &self.tabs[self.active]
+    }
+
+    pub fn send(&self, event: DetailEvent, cx: &mut App) {
+        self.active().update(cx, |_, cx| {
+            cx.emit(event);
+            cx.notify();
+        });
+    }
+
+    pub fn open(
+        &mut self,
+        href: SharedString,
+        fid: Option<u32>,
+        page: u32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let event = DetailEvent::Load(href, fid, page);
+        if self.is_blank(cx) {
+            self.send(event, cx);
+            cx.notify();
+        } else {
+            self.open_in_new_tab(event, window, cx);
+        }
+    }
+
+    pub fn open_in_new_tab(
+        &mut self,
+        event: DetailEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let detail = self.new_detail(window, cx);
+        self.active = (self.active + 1).min(self.tabs.len());
+        self.tabs.insert(self.active, detail);
+        self.send(event, cx);
+        cx.notify();
+    }
+
+    pub fn restore(
+        &mut self,
+        tab: Option<EntityId>,
+        thread: Option<ThreadLocation>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ix = tab.and_then(|tab| {
+            self.tabs
+                .iter()
+                .position(|detail| detail.entity_id() == tab)
+        });
+        match (ix, thread) {
+            (Some(ix), Some(thread)) => {
+                self.active = ix;
+                self.send(DetailEvent::Restore(thread), cx);
+            }
+            (Some(ix), None) => {
+                self.active = ix;
+                if let Some(location) = self.active().read(cx).location() {
+                    self.remember(location);
+                    self.send(DetailEvent::Close, cx);
+                }
+            }
+            (None, Some(thread)) => {
+                if self.is_blank(cx) {
+                    self.send(DetailEvent::Restore(thread), cx);
+                } else {
+                    self.open_in_new_tab(DetailEvent::Restore(thread), window, cx);
+                }
+            }
+            (None, None) => {}
+        }
+        cx.notify();
+    }
+
+    fn new_detail(&self, window: &mut Window, cx: &mut Context<Self>) -> Entity<Detail> {
+        let detail = cx.new(|cx| Detail::new(window, cx));
+        cx.subscribe(&detail, |this, _, event, cx| {
+            if let DetailEvent::Opened(previous) = event {
+                if previous.is_some() || this.tabs.len() == 1 {
+                    cx.emit(TabsEvent::Opened(previous.clone()));
+                }
+            }
+        })
+        .detach();
+        cx.observe(&detail, |_, _, cx| cx.notify()).detach();
+
+        detail
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix < self.tabs.len() {
+            self.active = ix;
+            cx.notify();
+        }
+    }
+
+    fn close(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(detail) = self.tabs.get(ix) else {
+            return;
+        };
+
+        if let Some(location) = detail.read(cx).location() {
+            self.remember(location);
+        }
+        if self.tabs.len() == 1 {
+            self.send(DetailEvent::Close, cx);
+        } else {
+            self.tabs.remove(ix);
+            if self.active > ix || self.active == self.tabs.len() {
+                self.active -= 1;
+            }
+        }
+        cx.notify();
+    }
+
+    fn remember(&mut self, location: ThreadLocation) {
+        self.closed.push(location);
+        if self.closed.len() > MAX_CLOSED {
+            self.closed.remove(0);
+        }
+    }
+
+    fn reopen_closed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(location) = self.closed.pop() else {
+            return;
+        };
+
+        if self.is_blank(cx) {
+            self.send(DetailEvent::Restore(location), cx);
+            cx.notify();
+        } else {
+            self.open_in_new_tab(DetailEvent::Restore(location), window, cx);
+        }
+    }
+
+    fn is_blank(&self, cx: &App) -> bool {
+        self.tabs.len() == 1 && self.active().read(cx).location().is_none()
+    }
+
+    fn move_tab(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from == to || from >= self.tabs.len() || to >= self.tabs.len() {
+            return;
+        }
+
+        let active = self.tabs[self.active].clone();
+        let detail = self.tabs.remove(from);
+        self.tabs.insert(to, detail);
+        self.active = self
+            .tabs
+            .iter()
+            .position(|detail| detail == &active)
+            .unwrap_or(0);
+        cx.notify();
+    }
+
+    fn render_tab(
+        &self,
+        ix: usize,
+        detail: &Entity<Detail>,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let title = detail
+            .read(cx)
+            .title()
+            .unwrap_or_else(|| SharedString::from("新标签页"));
+        let is_active = ix == self.active;
+        let id = detail.entity_id().as_u64() as usize;
+        let theme = cx.theme();
+
+        div()
+            .id(("DetailTab", id))
+            .w(TAB_WIDTH)
+            .flex()
+            .flex_none()
+            .items_center()
+            .gap_1()
+            .pl_2()
+            .rounded_md()
+            .cursor_pointer()
+            .hover(|style| style.bg(theme.secondary_hover))
+            .when(is_active, |this| this.bg(theme.secondary_active))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _, cx| this.select(ix, cx)),
+            )
+            .on_mouse_down(
+                MouseButton::Middle,
+                cx.listener(move |this, _, _, cx| this.close(ix, cx)),
+            )
+            .on_drag(
+                DraggedTab {
+                    ix,
+                    title: title.clone(),
+                },
+                |tab, _, _, cx| cx.new(|_| tab.clone()),
+            )
+            .on_drop(
+                cx.listener(move |this, tab: &DraggedTab, _, cx| this.move_tab(tab.ix, ix, cx)),
+            )
+            .child(div().flex_1().min_w_0().truncate().text_sm().child(title))
+            .child(
+                div()
+                    .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                    .child(
+                        Button::new(("DetailTabClose", id))
+                            .icon(IconName::X)
+                            .ghost()
+                            .xsmall()
+                            .cursor_pointer()
+                            .on_click(cx.listener(move |this, _, _, cx| this.close(ix, cx))),
+                    ),
+            )
+    }
+
+    fn render_strip(&self, cx: &Context<Self>) -> impl IntoElement {
+        let tabs = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(ix, detail)| self.render_tab(ix, detail, cx).into_any_element())
+            .collect::<Vec<_>>();
+        let theme = cx.theme();
+
+        div()
+            .w_full()
+            .p_1()
+            .flex()
+            .items_center()
+            .gap_1()
+            .border_b_1()
+            .border_color(theme.border)
+            .child(
+                div()
+                    .id("DetailTabs")
+                    .flex_1()
+                    .min_w_0()
+                    .flex()
+                    .gap_1()
+                    .overflow_x_scroll()
+                    .children(tabs),
+            )
+            .child(
+                Button::new("DetailTabReopen")
+                    .icon(IconName::RotateCcw)
+                    .ghost()
+                    .small()
+                    .cursor_pointer()
+                    .tooltip("恢复关闭的标签页")
+                    .disabled(self.closed.is_empty())
+                    .on_click(cx.listener(|this, _, window, cx| this.reopen_closed(window, cx))),
+            )
+    }
+}
+
+impl Render for DetailTabs {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let strip = self.render_strip(cx);
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .flex()
+            .flex_col()
+            .child(strip)
+            .child(div().flex_1().min_h_0().child(self.active().clone()))
+    }
+}
+
+pub enum TabsEvent {
+    Opened(Option<ThreadLocation>),
+}
+
+impl EventEmitter<TabsEvent> for DetailTabs {}
+
+#[derive(Clone)]
+struct DraggedTab {
+    ix: usize,
+    title: SharedString,
+}
+
+impl Render for DraggedTab {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+
+        div()
+            .w(TAB_WIDTH)
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .bg(theme.secondary_active)
+            .border_1()
+            .border_color(theme.border)
+            .truncate()
+            .text_sm()
+            .child(self.title.clone())
+    }
+}
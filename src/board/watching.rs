@@ -0,0 +1,223 @@
+use gpui::{
+    App, AppContext as _, Context, Entity, FocusHandle, InteractiveElement, IntoElement,
+    MouseButton, ParentElement as _, Pixels, Render, SharedString, StatefulInteractiveElement as _,
+    Styled as _, Window, div, prelude::FluentBuilder as _, px,
+};
+use gpui_component::button::{Button, ButtonVariants as _};
+use gpui_component::label::Label;
+use gpui_component::{
+    ActiveTheme as _, ContextModal as _, Selectable as _, Sizable as _, StyledExt as _,
+};
+
+use super::ViewState;
+use super::detail::DetailEvent;
+use super::tabs::DetailTabs;
+use crate::icon::IconName;
+use crate::watch::{Watch, WatchStore};
+
+const WATCHING_WIDTH: Pixels = px(700.);
+const INTERVAL_MINUTES: [u64; 4] = [5, 15, 30, 60];
+
+pub struct Watching {
+    watch_store: Entity<WatchStore>,
+    tabs: Entity<DetailTabs>,
+    focus_handle: FocusHandle,
+}
+
+impl Watching {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let watch_store = WatchStore::global(cx);
+        cx.observe(&watch_store, |_, _, cx| cx.notify()).detach();
+        let tabs = cx.new(|cx| DetailTabs::new(window, cx));
+
+        Self {
+            watch_store,
+            tabs,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn tabs(&self) -> &Entity<DetailTabs> {
+        &self.tabs
+    }
+
+    pub fn view_state(&self, cx: &App) -> ViewState {
+        ViewState::capture(None, &self.tabs, cx)
+    }
+
+    pub fn restore(&mut self, state: ViewState, window: &mut Window, cx: &mut Context<Self>) {
+        state.restore(None, &self.tabs, window, cx);
+    }
+
+    pub fn open(
+        &mut self,
+        href: SharedString,
+        fid: Option<u32>,
+        page: u32,
+        floor: u32,
+        cx: &mut Context<Self>,
+    ) {
+        self.tabs.update(cx, |tabs, cx| {
+            tabs.send(DetailEvent::LoadFloor(href, fid, page, floor), cx)
+        });
+    }
+
+    fn on_item_click(&mut self, watch: &Watch, cx: &mut Context<Self>) {
+        let (page, floor) = if watch.new_replies() > 0 {
+            (
+                watch.unseen_page.unwrap_or(watch.total_page),
+                watch.seen_count + 1,
+            )
+        } else {
+            (watch.total_page, watch.latest_count)
+        };
+        self.open(watch.href.clone(), watch.fid, page, floor, cx);
+    }
+
+    fn on_remove(&mut self, href: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let result = self
+            .watch_store
+            .update(cx, |watch_store, cx| watch_store.remove(&href, cx));
+        if let Err(error) = result {
+            window.push_notification(error.to_string(), cx);
+        }
+    }
+
+    fn on_interval_click(&mut self, minutes: u64, window: &mut Window, cx: &mut Context<Self>) {
+        let result = self.watch_store.update(cx, |watch_store, cx| {
+            watch_store.set_interval_minutes(minutes, cx)
+        });
+        if let Err(error) = result {
+            window.push_notification(error.to_string(), cx);
+        }
+    }
+
+    fn render_interval(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let interval_minutes = self.watch_store.read(cx).interval_minutes();
+        let buttons = INTERVAL_MINUTES.iter().map(|minutes| {
+            let minutes = *minutes;
+            Button::new(("WatchInterval", minutes as usize))
+                .label(format!("{minutes} 分钟"))
+                .ghost()
+                .small()
+                .cursor_pointer()
+                .selected(interval_minutes == minutes)
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.on_interval_click(minutes, window, cx)
+                }))
+        });
+        let theme = cx.theme();
+
+        div()
+            .p_2()
+            .flex()
+            .items_center()
+            .gap_1()
+            .border_b_1()
+            .border_color(theme.border)
+            .child(Label::new("检查间隔").text_sm())
+            .children(buttons)
+    }
+
+    fn render_watch(&self, watch: &Watch, cx: &mut Context<Self>) -> impl IntoElement {
+        let clicked = watch.clone();
+        let remove_href = watch.href.clone();
+        let new_replies = watch.new_replies();
+        let theme = cx.theme();
+
+        div()
+            .mt_2()
+            .mx_2()
+            .p_2()
+            .rounded_md()
+            .bg(theme.secondary_hover)
+            .border_1()
+            .border_color(theme.border)
+            .hover(|style| style.bg(theme.secondary_active))
+            .cursor_pointer()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _, cx| this.on_item_click(&clicked, cx)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_start()
+                    .justify_between()
+                    .gap_1()
+                    .child(Label::new(watch.title.clone()).font_semibold().text_lg())
+                    .child(
+                        div()
+                            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                            .child(
+                                Button::new(watch.href.clone())
+                                    .icon(IconName::Trash)
+                                    .ghost()
+                                    .small()
+                                    .cursor_pointer()
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.on_remove(remove_href.clone(), window, cx)
+                                    })),
+                            ),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .pt_1()
+                    .gap_1()
+                    .items_center()
+                    .child(
+                        Label::new(format!("#{}", watch.latest_count))
+                            .text_color(theme.primary_hover)
+                            .font_light()
+                            .text_sm(),
+                    )
+                    .when(new_replies > 0, |this| {
+                        this.child(
+                            Label::new(format!("+{new_replies} 新回复"))
+                                .text_color(theme.red)
+                                .text_sm(),
+                        )
+                    }),
+            )
+    }
+}
+
+impl Render for Watching {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let interval = self.render_interval(cx);
+        let threads = self.watch_store.read(cx).threads().to_vec();
+        let threads = threads
+            .iter()
+            .map(|watch| self.render_watch(watch, cx).into_any_element())
+            .collect::<Vec<_>>();
+        let theme = cx.theme();
+
+        div()
+            .track_focus(&self.focus_handle)
+            .w_full()
+            .h_full()
+            .flex()
+            .child(
+                div()
+                    .h_full()
+                    .w(WATCHING_WIDTH)
+                    .flex()
+                    .flex_col()
+                    .border_r_1()
+                    .border_color(theme.border)
+                    .child(interval)
+                    .child(
+                        div()
+                            .id("Watching")
+                            .flex_1()
+                            .min_h_0()
+                            .pb_2()
+                            .overflow_y_scroll()
+                            .children(threads),
+                    ),
+            )
+            .child(self.tabs.clone())
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+
+use chrono::{Local, NaiveDateTime};
+use gpui::{App, AppContext as _, Context, Entity, Global, SharedString};
+use serde::{Deserialize, Serialize};
+
+use crate::link;
+use crate::store;
+
+const BOOKMARKS: &str = "bookmarks";
+
+pub fn init(cx: &mut App) {
+    let mut bookmarks = store::load::<Vec<Bookmark>>(BOOKMARKS).unwrap_or_default();
+    if migrate(&mut bookmarks) {
+        store::save(BOOKMARKS, &bookmarks).ok();
+    }
+    let bookmark_store = cx.new(|_| BookmarkStore { bookmarks });
+    cx.set_global(GlobalBookmarkStore(bookmark_store));
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub title: SharedString,
+    pub href: SharedString,
+    pub fid: Option<u32>,
+    pub author: SharedString,
+    pub saved_at: NaiveDateTime,
+}
+
+impl Bookmark {
+    pub fn new(
+        title: SharedString,
+        href: SharedString,
+        fid: Option<u32>,
+        author: SharedString,
+    ) -> Self {
+        Self {
+            title,
+            href,
+            fid,
+            author,
+            saved_at: Local::now().naive_local(),
+        }
+    }
+}
+
+pub struct BookmarkStore {
+    bookmarks: Vec<Bookmark>,
+}
+
+impl BookmarkStore {
+    pub fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalBookmarkStore>().0.clone()
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    pub fn contains(&self, href: &str) -> bool {
+        let key = link::thread_key(href);
+        self.bookmarks
+            .iter()
+            .any(|bookmark| link::thread_key(&bookmark.href) == key)
+    }
+
+    pub fn toggle(&mut self, bookmark: Bookmark, cx: &mut Context<Self>) -> anyhow::Result<()> {
+        if self.contains(&bookmark.href) {
+            return self.remove(&bookmark.href, cx);
+        }
+
+        self.bookmarks.insert(0, bookmark);
+        cx.notify();
+
+        store::save(BOOKMARKS, &self.bookmarks)
+    }
+
+    pub fn remove(&mut self, href: &str, cx: &mut Context<Self>) -> anyhow::Result<()> {
+        let key = link::thread_key(href);
+        self.bookmarks
+            .retain(|bookmark| link::thread_key(&bookmark.href) != key);
+        cx.notify();
+
+        store::save(BOOKMARKS, &self.bookmarks)
+    }
+}
+
+fn migrate(bookmarks: &mut Vec<Bookmark>) -> bool {
+    let len = bookmarks.len();
+    let mut changed = false;
+    let mut keys = HashSet::new();
+    bookmarks.retain_mut(|bookmark| {
+        let href = link::thread_href(&bookmark.href);
+        if href != bookmark.href.as_ref() {
+            bookmark.href = href.into();
+            changed = true;
+        }
+
+        keys.insert(link::thread_key(&bookmark.href))
+    });
+
+    changed || bookmarks.len() != len
+}
+
+struct GlobalBookmarkStore(Entity<BookmarkStore>);
+
+impl Global for GlobalBookmarkStore {}
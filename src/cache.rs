@@ -0,0 +1,480 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use gpui::{App, BackgroundExecutor, Global};
+use http_client::http::{HeaderValue, Method, header};
+use http_client::{AsyncBody, Request, Response};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::mirror;
+use crate::page::PageKind;
+use crate::store;
+
+const CACHE_SETTINGS: &str = "cache_settings";
+const IMAGE_CACHE_SETTINGS: &str = "image_cache_settings";
+const IMAGE_INDEX: &str = "index.json";
+const CACHED_AT: &str = "x-bustop-cached-at";
+const CACHED_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const DEFAULT_FRESHNESS_MINUTES: u64 = 5;
+const DEFAULT_IMAGE_CACHE_MB: u64 = 200;
+const INDEX_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+static INDEX_SEQ: AtomicU64 = AtomicU64::new(0);
+
+pub fn cached_at<T>(response: &Response<T>) -> Option<NaiveDateTime> {
+    response
+        .headers()
+        .get(CACHED_AT)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| NaiveDateTime::parse_from_str(value, CACHED_AT_FORMAT).ok())
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct CacheSettings {
+    pub freshness_minutes: u64,
+    pub offline: bool,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            freshness_minutes: DEFAULT_FRESHNESS_MINUTES,
+            offline: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedPage {
+    url: String,
+    cached_at: NaiveDateTime,
+    body: String,
+}
+
+impl CachedPage {
+    fn into_response(self) -> anyhow::Result<Response<AsyncBody>> {
+        let cached_at = self.cached_at.format(CACHED_AT_FORMAT).to_string();
+        Response::builder()
+            .status(200)
+            .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .header(CACHED_AT, cached_at)
+            .extension(PageKind::Content)
+            .body(AsyncBody::from(self.body))
+            .map_err(|error| anyhow::anyhow!("构建响应失败 - {error}"))
+    }
+}
+
+pub struct PageCache {
+    settings: RwLock<CacheSettings>,
+    dir: PathBuf,
+}
+
+impl PageCache {
+    pub fn new() -> Self {
+        let settings = store::load::<CacheSettings>(CACHE_SETTINGS).unwrap_or_default();
+
+        Self {
+            settings: RwLock::new(settings),
+            dir: store::data_dir().join("cache").join("pages"),
+        }
+    }
+
+    pub fn global(cx: &App) -> Arc<Self> {
+        cx.global::<GlobalPageCache>().0.clone()
+    }
+
+    pub fn settings(&self) -> CacheSettings {
+        self.settings
+            .read()
+            .map(|settings| *settings)
+            .unwrap_or_default()
+    }
+
+    pub fn set_offline(&self, offline: bool) -> anyhow::Result<()> {
+        self.update_settings(|settings| settings.offline = offline)
+    }
+
+    pub fn set_freshness_minutes(&self, freshness_minutes: u64) -> anyhow::Result<()> {
+        self.update_settings(|settings| settings.freshness_minutes = freshness_minutes)
+    }
+
+    pub fn is_cacheable(request: &Request<AsyncBody>) -> bool {
+        if request.method() != Method::GET {
+            return false;
+        }
+        if request.headers().get(header::CACHE_CONTROL)
+            == Some(&HeaderValue::from_static("no-cache"))
+        {
+            return false;
+        }
+
+        let uri = request.uri();
+        mirror::is_mirror(uri.host())
+            && (uri.path().ends_with(".php") || uri.path().ends_with(".html"))
+    }
+
+    pub fn is_fresh(&self, cached_at: NaiveDateTime) -> bool {
+        let freshness_minutes = self.settings().freshness_minutes as i64;
+
+        Local::now().naive_local() - cached_at < TimeDelta::minutes(freshness_minutes)
+    }
+
+    pub async fn load(&self, url: &str) -> Option<(NaiveDateTime, Response<AsyncBody>)> {
+        let text = smol::fs::read_to_string(self.path(url)).await.ok()?;
+        let page = serde_json::from_str::<CachedPage>(&text).ok()?;
+        if page.url != url {
+            return None;
+        }
+
+        let cached_at = page.cached_at;
+        let response = page.into_response().ok()?;
+
+        Some((cached_at, response))
+    }
+
+    pub async fn save(&self, url: &str, body: &str) -> anyhow::Result<()> {
+        smol::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|error| anyhow::anyhow!("创建目录失败 - {error}"))?;
+        let page = CachedPage {
+            url: url.to_string(),
+            cached_at: Local::now().naive_local(),
+            body: body.to_string(),
+        };
+        let text = serde_json::to_string(&page)
+            .map_err(|error| anyhow::anyhow!("序列化失败 - {error}"))?;
+        smol::fs::write(self.path(url), text)
+            .await
+            .map_err(|error| anyhow::anyhow!("写入文件失败 - {error}"))?;
+
+        Ok(())
+    }
+
+    pub fn migrate(&self) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(mut page) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|text| serde_json::from_str::<CachedPage>(&text).ok())
+            else {
+                continue;
+            };
+            let key = mirror::resource_key(&page.url);
+            if key == page.url {
+                continue;
+            }
+
+            let new_path = self.path(&key);
+            page.url = key;
+            if !new_path.exists() {
+                let Ok(text) = serde_json::to_string(&page) else {
+                    continue;
+                };
+                if fs::write(&new_path, text).is_err() {
+                    continue;
+                }
+            }
+            fs::remove_file(&path).ok();
+        }
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        let key = Sha256::digest(url.as_bytes());
+
+        self.dir.join(format!("{key:x}.json"))
+    }
+
+    fn update_settings(&self, update: impl FnOnce(&mut CacheSettings)) -> anyhow::Result<()> {
+        let mut settings = self
+            .settings
+            .write()
+            .map_err(|_| anyhow::anyhow!("读取设置失败"))?;
+        update(&mut settings);
+
+        store::save(CACHE_SETTINGS, &*settings)
+    }
+}
+
+pub struct GlobalPageCache(pub Arc<PageCache>);
+
+impl Global for GlobalPageCache {}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct ImageCacheSettings {
+    pub max_mb: u64,
+}
+
+impl Default for ImageCacheSettings {
+    fn default() -> Self {
+        Self {
+            max_mb: DEFAULT_IMAGE_CACHE_MB,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct ImageEntry {
+    hash: String,
+    size: u64,
+    content_type: Option<String>,
+    last_access: NaiveDateTime,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ImageIndex {
+    entries: HashMap<String, ImageEntry>,
+}
+
+impl ImageIndex {
+    fn usage(&self) -> u64 {
+        let mut hashes = HashSet::new();
+        self.entries
+            .values()
+            .filter(|entry| hashes.insert(entry.hash.as_str()))
+            .map(|entry| entry.size)
+            .sum()
+    }
+
+    fn evict(&mut self, max_bytes: u64) -> Vec<String> {
+        let mut usage = self.usage();
+        if usage <= max_bytes {
+            return Vec::new();
+        }
+
+        let mut objects = HashMap::<&str, (NaiveDateTime, u64)>::new();
+        for entry in self.entries.values() {
+            let object = objects
+                .entry(entry.hash.as_str())
+                .or_insert((entry.last_access, entry.size));
+            object.0 = object.0.max(entry.last_access);
+        }
+        let mut objects = objects.into_iter().collect::<Vec<_>>();
+        objects.sort_by_key(|(_, (last_access, _))| *last_access);
+
+        let mut removed = HashSet::new();
+        for (hash, (_, size)) in objects {
+            if usage <= max_bytes {
+                break;
+            }
+            usage = usage.saturating_sub(size);
+            removed.insert(hash.to_string());
+        }
+        self.entries
+            .retain(|_, entry| !removed.contains(&entry.hash));
+
+        removed.into_iter().collect()
+    }
+}
+
+pub struct ImageCache {
+    settings: RwLock<ImageCacheSettings>,
+    index: Arc<Mutex<ImageIndex>>,
+    dir: PathBuf,
+    executor: BackgroundExecutor,
+    is_saving: Arc<AtomicBool>,
+}
+
+impl ImageCache {
+    pub fn new(executor: BackgroundExecutor) -> Self {
+        let settings = store::load::<ImageCacheSettings>(IMAGE_CACHE_SETTINGS).unwrap_or_default();
+        let dir = store::data_dir().join("cache").join("images");
+        let mut index = fs::read_to_string(dir.join(IMAGE_INDEX))
+            .ok()
+            .and_then(|text| serde_json::from_str::<ImageIndex>(&text).ok())
+            .unwrap_or_default();
+        index.entries = index
+            .entries
+            .into_iter()
+            .map(|(url, entry)| (mirror::resource_key(&url), entry))
+            .collect();
+
+        Self {
+            settings: RwLock::new(settings),
+            index: Arc::new(Mutex::new(index)),
+            dir,
+            executor,
+            is_saving: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn global(cx: &App) -> Arc<Self> {
+        cx.global::<GlobalImageCache>().0.clone()
+    }
+
+    pub fn settings(&self) -> ImageCacheSettings {
+        self.settings
+            .read()
+            .map(|settings| *settings)
+            .unwrap_or_default()
+    }
+
+    pub fn usage(&self) -> u64 {
+        self.index.lock().map(|index| index.usage()).unwrap_or(0)
+    }
+
+    pub fn set_max_mb(&self, max_mb: u64) -> anyhow::Result<()> {
+        let settings = {
+            let mut settings = self
+                .settings
+                .write()
+                .map_err(|_| anyhow::anyhow!("读取设置失败"))?;
+            settings.max_mb = max_mb;
+            *settings
+        };
+        store::save(IMAGE_CACHE_SETTINGS, &settings)?;
+
+        let removed = self
+            .index
+            .lock()
+            .map_err(|_| anyhow::anyhow!("读取缓存失败"))?
+            .evict(max_mb * 1024 * 1024);
+        for hash in removed {
+            fs::remove_file(self.object_path(&hash)).ok();
+        }
+        self.save_index();
+
+        Ok(())
+    }
+
+    pub fn clear(&self) -> anyhow::Result<()> {
+        self.index
+            .lock()
+            .map_err(|_| anyhow::anyhow!("读取缓存失败"))?
+            .entries
+            .clear();
+        let objects = self.dir.join("objects");
+        if objects.exists() {
+            fs::remove_dir_all(objects)
+                .map_err(|error| anyhow::anyhow!("删除缓存失败 - {error}"))?;
+        }
+        self.save_index();
+
+        Ok(())
+    }
+
+    pub async fn load(&self, url: &str) -> Option<Response<AsyncBody>> {
+        let entry = {
+            let mut index = self.index.lock().ok()?;
+            let entry = index.entries.get_mut(url)?;
+            entry.last_access = Local::now().naive_local();
+            entry.clone()
+        };
+        let Ok(bytes) = smol::fs::read(self.object_path(&entry.hash)).await else {
+            if let Ok(mut index) = self.index.lock() {
+                index.entries.remove(url);
+            }
+            self.save_index();
+            return None;
+        };
+        self.save_index();
+
+        let mut response = Response::builder().status(200);
+        if let Some(content_type) = entry.content_type {
+            response = response.header(header::CONTENT_TYPE, content_type);
+        }
+
+        response.body(AsyncBody::from(bytes)).ok()
+    }
+
+    pub async fn save(
+        &self,
+        url: &str,
+        content_type: Option<String>,
+        bytes: &[u8],
+    ) -> anyhow::Result<()> {
+        let hash = format!("{:x}", Sha256::digest(bytes));
+        let path = self.object_path(&hash);
+        if !path.exists() {
+            smol::fs::create_dir_all(self.dir.join("objects"))
+                .await
+                .map_err(|error| anyhow::anyhow!("创建目录失败 - {error}"))?;
+            smol::fs::write(&path, bytes)
+                .await
+                .map_err(|error| anyhow::anyhow!("写入文件失败 - {error}"))?;
+        }
+
+        let max_bytes = self.settings().max_mb * 1024 * 1024;
+        let removed = {
+            let mut index = self
+                .index
+                .lock()
+                .map_err(|_| anyhow::anyhow!("读取缓存失败"))?;
+            index.entries.insert(
+                url.to_string(),
+                ImageEntry {
+                    hash,
+                    size: bytes.len() as u64,
+                    content_type,
+                    last_access: Local::now().naive_local(),
+                },
+            );
+            index.evict(max_bytes)
+        };
+        for hash in removed {
+            smol::fs::remove_file(self.object_path(&hash)).await.ok();
+        }
+        self.save_index();
+
+        Ok(())
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.dir.join("objects").join(hash)
+    }
+
+    fn save_index(&self) {
+        if self.is_saving.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let index = self.index.clone();
+        let dir = self.dir.clone();
+        let is_saving = self.is_saving.clone();
+        self.executor
+            .spawn(async move {
+                smol::Timer::after(INDEX_SAVE_DELAY).await;
+                is_saving.store(false, Ordering::Release);
+                let text = index
+                    .lock()
+                    .ok()
+                    .and_then(|index| serde_json::to_string(&*index).ok());
+                if let Some(text) = text {
+                    write_index(&dir, text).await.ok();
+                }
+            })
+            .detach();
+    }
+}
+
+async fn write_index(dir: &Path, text: String) -> anyhow::Result<()> {
+    smol::fs::create_dir_all(dir)
+        .await
+        .map_err(|error| anyhow::anyhow!("创建目录失败 - {error}"))?;
+    let tmp_path = dir.join(format!(
+        "{IMAGE_INDEX}.{}.{}.tmp",
+        std::process::id(),
+        INDEX_SEQ.fetch_add(1, Ordering::Relaxed)
+    ));
+    smol::fs::write(&tmp_path, text)
+        .await
+        .map_err(|error| anyhow::anyhow!("写入文件失败 - {error}"))?;
+    if let Err(error) = smol::fs::rename(&tmp_path, dir.join(IMAGE_INDEX)).await {
+        smol::fs::remove_file(&tmp_path).await.ok();
+        anyhow::bail!("写入文件失败 - {error}");
+    }
+
+    Ok(())
+}
+
+pub struct GlobalImageCache(pub Arc<ImageCache>);
+
+impl Global for GlobalImageCache {}
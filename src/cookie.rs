@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+use gpui::{App, Global};
+use http_client::http::{HeaderMap, header};
+use serde::{Deserialize, Serialize};
+use smol::channel::{Receiver, Sender};
+
+use crate::store;
+
+const COOKIES: &str = "cookies";
+const GUEST_UID: &str = "discuz_uid = '0'";
+const VOLATILE_COOKIES: [&str; 8] = [
+    "_lastact",
+    "_lastvisit",
+    "_lastrequest",
+    "_sid",
+    "_st_t",
+    "_visitedfid",
+    "_ulastactivity",
+    "_creditnotice",
+];
+
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Session {
+    username: Option<String>,
+    cookies: BTreeMap<String, String>,
+}
+
+impl Session {
+    fn persisted(&self) -> Self {
+        let cookies = self
+            .cookies
+            .iter()
+            .filter(|(name, _)| !VOLATILE_COOKIES.iter().any(|suffix| name.ends_with(suffix)))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        Self {
+            username: self.username.clone(),
+            cookies,
+        }
+    }
+}
+
+pub struct CookieJar {
+    session: RwLock<Session>,
+    save_lock: Mutex<()>,
+    expired_tx: Sender<()>,
+    expired_rx: Receiver<()>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        let session = store::load::<Session>(COOKIES).unwrap_or_default();
+        let (expired_tx, expired_rx) = smol::channel::unbounded();
+
+        Self {
+            session: RwLock::new(session),
+            save_lock: Mutex::new(()),
+            expired_tx,
+            expired_rx,
+        }
+    }
+
+    pub fn global(cx: &App) -> Arc<Self> {
+        cx.global::<GlobalCookieJar>().0.clone()
+    }
+
+    pub fn username(&self) -> Option<String> {
+        self.session
+            .read()
+            .ok()
+            .and_then(|session| session.username.clone())
+    }
+
+    pub fn expired(&self) -> Receiver<()> {
+        self.expired_rx.clone()
+    }
+
+    pub fn header(&self) -> Option<String> {
+        let session = self.session.read().ok()?;
+        if session.cookies.is_empty() {
+            return None;
+        }
+
+        let cookies = session
+            .cookies
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>();
+
+        Some(cookies.join("; "))
+    }
+
+    pub fn has_auth(&self) -> bool {
+        self.session
+            .read()
+            .map(|session| session.cookies.keys().any(|name| name.ends_with("_auth")))
+            .unwrap_or(false)
+    }
+
+    pub fn store(&self, headers: &HeaderMap) {
+        let cookies = headers
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .flat_map(|value| value.to_str().ok())
+            .flat_map(parse_set_cookie)
+            .collect::<Vec<_>>();
+        if cookies.is_empty() {
+            return;
+        }
+
+        self.update(|session| {
+            for (name, value) in cookies {
+                match value {
+                    Some(value) => session.cookies.insert(name, value),
+                    None => session.cookies.remove(&name),
+                };
+            }
+        })
+        .ok();
+    }
+
+    pub fn add(&self, cookies: &str) {
+        let cookies = cookies
+            .split(';')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .filter(|(name, _)| !name.is_empty())
+            .collect::<Vec<_>>();
+        if cookies.is_empty() {
+            return;
+        }
+
+        self.update(|session| session.cookies.extend(cookies)).ok();
+    }
+
+    pub fn login(&self, username: String) -> anyhow::Result<()> {
+        anyhow::ensure!(self.has_auth(), "登录失败 - 没有收到登录凭证");
+
+        self.update(|session| session.username = Some(username))
+    }
+
+    pub fn logout(&self) -> anyhow::Result<()> {
+        self.update(|session| *session = Session::default())
+    }
+
+    pub fn check_session(&self, text: &str) {
+        if self.username().is_none() || !text.contains(GUEST_UID) {
+            return;
+        }
+
+        if self.logout().is_ok() {
+            self.expired_tx.try_send(()).ok();
+        }
+    }
+
+    fn update(&self, update: impl FnOnce(&mut Session)) -> anyhow::Result<()> {
+        let is_changed = {
+            let mut session = self
+                .session
+                .write()
+                .map_err(|_| anyhow::anyhow!("更新登录状态失败"))?;
+            let before = session.persisted();
+            update(&mut session);
+            session.persisted() != before
+        };
+        if !is_changed {
+            return Ok(());
+        }
+
+        let _save_lock = self
+            .save_lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("更新登录状态失败"))?;
+        let session = self
+            .session
+            .read()
+            .map_err(|_| anyhow::anyhow!("更新登录状态失败"))?
+            .persisted();
+
+        store::save_private(COOKIES, &session)
+    }
+}
+
+fn parse_set_cookie(value: &str) -> Option<(String, Option<String>)> {
+    let mut parts = value.split(';').map(|part| part.trim());
+    let (name, value) = parts.next()?.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let is_removed = value.is_empty()
+        || value == "deleted"
+        || parts.any(|part| {
+            part.to_ascii_lowercase()
+                .strip_prefix("max-age=")
+                .and_then(|age| age.parse::<i64>().ok())
+                .is_some_and(|age| age <= 0)
+        });
+
+    Some((name.to_string(), (!is_removed).then(|| value.to_string())))
+}
+
+pub struct GlobalCookieJar(pub Arc<CookieJar>);
+
+impl Global for GlobalCookieJar {}
@@ -0,0 +1,85 @@
+use gpui::{
+    App, AppContext as _, Context, Entity, FocusHandle, InteractiveElement as _, IntoElement,
+    ParentElement as _, Render, Styled as _, WeakEntity, Window, div, px,
+};
+use gpui_component::button::Button;
+use gpui_component::input::{InputEvent, InputState, TextInput};
+use gpui_component::{ContextModal as _, Sizable as _};
+
+use crate::Bustop;
+use crate::link::ThreadLink;
+
+pub struct GotoForm {
+    bustop: WeakEntity<Bustop>,
+    link_state: Entity<InputState>,
+    focus_handle: FocusHandle,
+}
+
+impl GotoForm {
+    pub fn open(bustop: WeakEntity<Bustop>, window: &mut Window, cx: &mut App) {
+        let form = cx.new(|cx| Self::new(bustop, window, cx));
+        let link_state = form.read(cx).link_state.clone();
+        window.open_modal(cx, move |modal, _, _| {
+            modal.title("打开链接").width(px(480.)).child(form.clone())
+        });
+        link_state.update(cx, |state, cx| state.focus(window, cx));
+    }
+
+    fn new(bustop: WeakEntity<Bustop>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let link_state = cx
+            .new(|cx| InputState::new(window, cx).placeholder("帖子链接, 如 thread-123-1-1.html"));
+        cx.subscribe_in(&link_state, window, Self::on_input_event)
+            .detach();
+
+        Self {
+            bustop,
+            link_state,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::PressEnter { secondary: _ } = event {
+            self.submit(window, cx);
+        }
+    }
+
+    fn submit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let value = self.link_state.read(cx).value().to_string();
+        let Some(link) = ThreadLink::parse(&value) else {
+            window.push_notification("无法识别的帖子链接", cx);
+            return;
+        };
+
+        window.close_modal(cx);
+        self.bustop
+            .update(cx, |bustop, cx| bustop.open_thread(link, window, cx))
+            .ok();
+    }
+}
+
+impl Render for GotoForm {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .track_focus(&self.focus_handle)
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(TextInput::new(&self.link_state))
+            .child(
+                div().flex().justify_end().child(
+                    Button::new("GotoSubmit")
+                        .label("打开")
+                        .small()
+                        .cursor_pointer()
+                        .on_click(cx.listener(|this, _, window, cx| this.submit(window, cx))),
+                ),
+            )
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDateTime};
+use gpui::{App, AppContext as _, Context, Entity, Global, Task};
+use serde::{Deserialize, Serialize};
+
+use crate::link;
+use crate::store;
+
+const HISTORY: &str = "history";
+const MAX_VISITS: usize = 5000;
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+pub fn init(cx: &mut App) {
+    let visits = store::load::<HashMap<String, Visit>>(HISTORY).unwrap_or_default();
+    let history_store = cx.new(|_| HistoryStore {
+        visits,
+        save_task: None,
+    });
+    cx.set_global(GlobalHistoryStore(history_store));
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Visit {
+    pub reply: u32,
+    pub last_reply_at: Option<NaiveDateTime>,
+    pub visited_at: NaiveDateTime,
+}
+
+pub struct HistoryStore {
+    visits: HashMap<String, Visit>,
+    save_task: Option<Task<()>>,
+}
+
+impl HistoryStore {
+    pub fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalHistoryStore>().0.clone()
+    }
+
+    pub fn visit(&self, href: &str) -> Option<&Visit> {
+        self.visits.get(&link::thread_key(href))
+    }
+
+    pub fn record(
+        &mut self,
+        href: &str,
+        reply: u32,
+        last_reply_at: Option<NaiveDateTime>,
+        cx: &mut Context<Self>,
+    ) {
+        let visit = Visit {
+            reply,
+            last_reply_at,
+            visited_at: Local::now().naive_local(),
+        };
+        self.visits.insert(link::thread_key(href), visit);
+        if self.visits.len() > MAX_VISITS {
+            let mut visited_at = self
+                .visits
+                .values()
+                .map(|visit| visit.visited_at)
+                .collect::<Vec<_>>();
+            visited_at.sort_unstable();
+            let oldest_kept = visited_at[visited_at.len() - MAX_VISITS];
+            self.visits
+                .retain(|_, visit| visit.visited_at >= oldest_kept);
+        }
+        self.save_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SAVE_DELAY).await;
+            let Ok(visits) = this.read_with(cx, |this, _| this.visits.clone()) else {
+                return;
+            };
+            cx.background_spawn(async move { store::save(HISTORY, &visits) })
+                .await
+                .ok();
+        }));
+        cx.notify();
+    }
+}
+
+struct GlobalHistoryStore(Entity<HistoryStore>);
+
+impl Global for GlobalHistoryStore {}
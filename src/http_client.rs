@@ -1,25 +1,353 @@
-use std::sync::Arc;
+use std::fmt;
+use std::sync::{Arc, RwLock};
 
+use futures::FutureExt as _;
 use futures::future::BoxFuture;
-use gpui::App;
-use http_client::http::{HeaderValue, header};
+use gpui::{App, Global};
+use http_client::http::request::Parts;
+use http_client::http::{HeaderValue, Method, StatusCode, Uri, header};
 use http_client::{AsyncBody, HttpClient, Request, Response, Url};
 use reqwest_client::ReqwestClient;
+use smol::io::AsyncReadExt as _;
+
+use crate::cache::{GlobalImageCache, GlobalPageCache, ImageCache, PageCache};
+use crate::cookie::{CookieJar, GlobalCookieJar};
+use crate::mirror;
+use crate::page::{AgeCookie, PageKind};
+use crate::proxy::ProxySettings;
+
+const USER_AGENT: &str = "bustop";
 
 pub fn init(cx: &mut App) {
-    let http_client = MultiHttpClient::new();
-    cx.set_http_client(Arc::new(http_client));
+    let page_cache = Arc::new(PageCache::new());
+    cx.set_global(GlobalPageCache(page_cache.clone()));
+    cx.background_spawn({
+        let page_cache = page_cache.clone();
+        async move { page_cache.migrate() }
+    })
+    .detach();
+    let image_cache = Arc::new(ImageCache::new(cx.background_executor().clone()));
+    cx.set_global(GlobalImageCache(image_cache.clone()));
+    let cookie_jar = Arc::new(CookieJar::new());
+    cx.set_global(GlobalCookieJar(cookie_jar.clone()));
+    let http_client = Arc::new(MultiHttpClient::new(page_cache, image_cache, cookie_jar));
+    cx.set_global(GlobalHttpClient(http_client.clone()));
+    cx.set_http_client(http_client);
 }
 
-pub struct MultiHttpClient {
+struct ProxyClient {
+    settings: ProxySettings,
     client: ReqwestClient,
 }
 
+impl ProxyClient {
+    fn new(settings: ProxySettings) -> anyhow::Result<Option<Self>> {
+        if !settings.enabled {
+            return Ok(None);
+        }
+
+        let client = ReqwestClient::proxy_and_user_agent(Some(settings.url()?), USER_AGENT)
+            .map_err(|error| anyhow::anyhow!("创建代理失败 - {error}"))?;
+
+        Ok(Some(Self { settings, client }))
+    }
+}
+
+struct Route {
+    client: Arc<ReqwestClient>,
+    proxy: Option<Arc<ProxyClient>>,
+}
+
+impl Route {
+    fn send(
+        &self,
+        req: Request<AsyncBody>,
+    ) -> BoxFuture<'static, anyhow::Result<Response<AsyncBody>>> {
+        if let Some(proxy) = self.proxy.as_ref() {
+            if !proxy.settings.bypasses(req.uri().host()) {
+                return proxy.client.send(req);
+            }
+        }
+
+        self.client.send(req)
+    }
+}
+
+pub struct MultiHttpClient {
+    client: Arc<ReqwestClient>,
+    proxy: RwLock<Option<Arc<ProxyClient>>>,
+    page_cache: Arc<PageCache>,
+    image_cache: Arc<ImageCache>,
+    cookie_jar: Arc<CookieJar>,
+}
+
 impl MultiHttpClient {
-    fn new() -> Self {
-        let client = ReqwestClient::user_agent("bustop").expect("failed to create http client");
+    fn new(
+        page_cache: Arc<PageCache>,
+        image_cache: Arc<ImageCache>,
+        cookie_jar: Arc<CookieJar>,
+    ) -> Self {
+        let client = ReqwestClient::user_agent(USER_AGENT).expect("failed to create http client");
+        let proxy = ProxyClient::new(ProxySettings::load())
+            .ok()
+            .flatten()
+            .map(Arc::new);
+
+        Self {
+            client: Arc::new(client),
+            proxy: RwLock::new(proxy),
+            page_cache,
+            image_cache,
+            cookie_jar,
+        }
+    }
+
+    pub fn global(cx: &App) -> Arc<Self> {
+        cx.global::<GlobalHttpClient>().0.clone()
+    }
+
+    pub fn proxy_settings(&self) -> ProxySettings {
+        self.proxy
+            .read()
+            .ok()
+            .and_then(|proxy| proxy.as_ref().map(|proxy| proxy.settings.clone()))
+            .unwrap_or_else(ProxySettings::load)
+    }
+
+    pub fn set_proxy(&self, settings: ProxySettings) -> anyhow::Result<()> {
+        let proxy = ProxyClient::new(settings.clone())?.map(Arc::new);
+        settings.save()?;
+        *self
+            .proxy
+            .write()
+            .map_err(|_| anyhow::anyhow!("更新代理失败"))? = proxy;
 
-        Self { client }
+        Ok(())
+    }
+
+    fn route(&self) -> Route {
+        Route {
+            client: self.client.clone(),
+            proxy: self.proxy.read().ok().and_then(|proxy| proxy.clone()),
+        }
+    }
+
+    fn send_network(
+        &self,
+        req: Request<AsyncBody>,
+    ) -> BoxFuture<'static, anyhow::Result<Response<AsyncBody>>> {
+        let route = self.route();
+        if !mirror::is_mirror(req.uri().host()) {
+            return route.send(req);
+        }
+
+        let cookie_jar = self.cookie_jar.clone();
+        if req.method() != Method::GET {
+            let response = route.send(req);
+            return async move {
+                let response = response.await?;
+                cookie_jar.store(response.headers());
+
+                Ok(response)
+            }
+            .boxed();
+        }
+
+        let (parts, _) = req.into_parts();
+        let hosts = mirror::failover_hosts();
+        async move {
+            let mut last = None;
+            let mut tried = 0;
+            for host in hosts {
+                let req = mirror_request(&parts, &host)?;
+                tried += 1;
+                match route.send(req).await {
+                    Ok(response) if !is_blocked(response.status()) => {
+                        cookie_jar.store(response.headers());
+                        mirror::set_active(&host);
+                        return Ok(response);
+                    }
+                    result => last = Some(result),
+                }
+            }
+
+            match last {
+                Some(Ok(mut response)) => {
+                    response.extensions_mut().insert(MirrorsTried(tried));
+                    Ok(response)
+                }
+                Some(Err(error)) => Err(FailoverError { tried, error }.into()),
+                None => Err(anyhow::anyhow!("没有可用的镜像")),
+            }
+        }
+        .boxed()
+    }
+
+    fn send_cached(
+        &self,
+        req: Request<AsyncBody>,
+    ) -> BoxFuture<'static, anyhow::Result<Response<AsyncBody>>> {
+        let url = req.uri().to_string();
+        let key = self.cache_key(req.uri());
+        let page_cache = self.page_cache.clone();
+        let cookie_jar = self.cookie_jar.clone();
+        let response = (!page_cache.settings().offline).then(|| self.send_network(req));
+
+        async move {
+            let cached = page_cache.load(&key).await;
+            let Some(response) = response else {
+                return cached
+                    .map(|(_, response)| response)
+                    .ok_or_else(|| anyhow::anyhow!("离线模式下没有缓存 - {url}"));
+            };
+            let stale = match cached {
+                Some((cached_at, response)) if page_cache.is_fresh(cached_at) => {
+                    return Ok(response);
+                }
+                Some((_, response)) => Some(response),
+                None => None,
+            };
+
+            match response.await {
+                Ok(response) if response.status().is_success() => {
+                    let (parts, mut body) = response.into_parts();
+                    let mut text = String::new();
+                    body.read_to_string(&mut text)
+                        .await
+                        .map_err(|error| anyhow::anyhow!("读取内容失败 - {error}"))?;
+                    cookie_jar.check_session(&text);
+                    let kind = PageKind::classify(&parts.headers, &text);
+                    if kind == PageKind::Content {
+                        page_cache.save(&key, &text).await.ok();
+                    }
+                    let mut response = Response::from_parts(parts, AsyncBody::from(text));
+                    response.extensions_mut().insert(kind);
+
+                    Ok(response)
+                }
+                Ok(response) => Ok(response),
+                Err(error) => stale.ok_or(error),
+            }
+        }
+        .boxed()
+    }
+
+    fn cache_key(&self, uri: &Uri) -> String {
+        let key = mirror::resource_key(&uri.to_string());
+        match self.cookie_jar.username() {
+            Some(username) => format!("{key}#{username}"),
+            None => key,
+        }
+    }
+
+    fn send_offline(
+        &self,
+        req: Request<AsyncBody>,
+    ) -> BoxFuture<'static, anyhow::Result<Response<AsyncBody>>> {
+        let url = req.uri().to_string();
+        let key = self.cache_key(req.uri());
+        let is_get = req.method() == Method::GET;
+        let page_cache = self.page_cache.clone();
+
+        async move {
+            if !is_get {
+                anyhow::bail!("离线模式下无法发送请求 - {url}");
+            }
+
+            page_cache
+                .load(&key)
+                .await
+                .map(|(_, response)| response)
+                .ok_or_else(|| anyhow::anyhow!("离线模式下没有缓存 - {url}"))
+        }
+        .boxed()
+    }
+
+    fn send_image(
+        &self,
+        req: Request<AsyncBody>,
+    ) -> BoxFuture<'static, anyhow::Result<Response<AsyncBody>>> {
+        let url = req.uri().to_string();
+        let key = mirror::resource_key(&url);
+        let image_cache = self.image_cache.clone();
+        let response = (!self.page_cache.settings().offline).then(|| self.send_network(req));
+
+        async move {
+            if let Some(response) = image_cache.load(&key).await {
+                return Ok(response);
+            }
+            let Some(response) = response else {
+                anyhow::bail!("离线模式下没有缓存 - {url}");
+            };
+
+            let response = response.await?;
+            if !response.status().is_success() {
+                return Ok(response);
+            }
+            let (parts, mut body) = response.into_parts();
+            let mut bytes = Vec::new();
+            body.read_to_end(&mut bytes)
+                .await
+                .map_err(|error| anyhow::anyhow!("读取内容失败 - {error}"))?;
+            let content_type = parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from);
+            image_cache.save(&key, content_type, &bytes).await.ok();
+
+            Ok(Response::from_parts(parts, AsyncBody::from(bytes)))
+        }
+        .boxed()
+    }
+
+    fn dispatch(
+        &self,
+        req: Request<AsyncBody>,
+        host: &HostSite,
+    ) -> BoxFuture<'static, anyhow::Result<Response<AsyncBody>>> {
+        if PageCache::is_cacheable(&req) {
+            return self.send_cached(req);
+        }
+        if host.is_image(req.method(), req.uri().path()) {
+            return self.send_image(req);
+        }
+        if self.page_cache.settings().offline && mirror::is_mirror(req.uri().host()) {
+            return self.send_offline(req);
+        }
+
+        self.send_network(req)
+    }
+
+    fn send_age_verified(
+        &self,
+        req: Request<AsyncBody>,
+        host: &HostSite,
+        age_cookie: AgeCookie,
+    ) -> BoxFuture<'static, anyhow::Result<Response<AsyncBody>>> {
+        let cookie_jar = self.cookie_jar.clone();
+        let response = self.dispatch(req, host);
+
+        async move {
+            let (parts, mut body) = response.await?.into_parts();
+            let mut text = String::new();
+            body.read_to_string(&mut text)
+                .await
+                .map_err(|error| anyhow::anyhow!("读取内容失败 - {error}"))?;
+            let kind = parts
+                .extensions
+                .get::<PageKind>()
+                .copied()
+                .unwrap_or_else(|| PageKind::classify(&parts.headers, &text));
+            if kind == PageKind::Content {
+                cookie_jar.add(&age_cookie.0);
+            }
+            let mut response = Response::from_parts(parts, AsyncBody::from(text));
+            response.extensions_mut().insert(kind);
+
+            Ok(response)
+        }
+        .boxed()
     }
 }
 
@@ -38,30 +366,34 @@ impl HttpClient for MultiHttpClient {
     ) -> BoxFuture<'static, anyhow::Result<Response<AsyncBody>>> {
         let uri = req.uri();
         let host = HostSite::from(uri.host());
-        let headers = req.headers_mut();
-        match host {
-            HostSite::Avatar => {
-                headers.insert(
-                    header::REFERER,
-                    HeaderValue::from_static("https://www.javbus.com/"),
-                );
+        if let HostSite::Preview = host {
+            if let Ok(uri) = mirror_uri(uri, &mirror::active_host()) {
+                *req.uri_mut() = uri;
             }
-            HostSite::Preview => {
-                headers.insert(
-                    header::REFERER,
-                    HeaderValue::from_static("https://www.javbus.com/forum/forum.php"),
-                );
+            if let Some(cookies) = self.cookie_jar.header() {
+                let cookies = match req.headers().get(header::COOKIE) {
+                    Some(cookie) => format!("{}; {cookies}", cookie.to_str().unwrap_or_default()),
+                    None => cookies,
+                };
+                if let Ok(cookies) = HeaderValue::from_str(&cookies) {
+                    req.headers_mut().insert(header::COOKIE, cookies);
+                }
             }
-            HostSite::Image => {
-                headers.insert(
-                    header::REFERER,
-                    HeaderValue::from_static("https://www.javbus.com/"),
-                );
-            }
-            HostSite::Unknown => (),
+        }
+        let referer = match host {
+            HostSite::Avatar => Some(format!("{}/", mirror::base_url())),
+            HostSite::Preview => Some(mirror::forum_url("forum.php")),
+            HostSite::Image => Some(format!("{}/", mirror::base_url())),
+            HostSite::Unknown => None,
+        };
+        if let Some(referer) = referer.and_then(|referer| HeaderValue::from_str(&referer).ok()) {
+            req.headers_mut().insert(header::REFERER, referer);
         }
 
-        self.client.send(req)
+        match req.extensions_mut().remove::<AgeCookie>() {
+            Some(age_cookie) => self.send_age_verified(req, &host, age_cookie),
+            None => self.dispatch(req, &host),
+        }
     }
 
     fn proxy(&self) -> Option<&Url> {
@@ -76,16 +408,91 @@ enum HostSite {
     Unknown,
 }
 
+impl HostSite {
+    fn is_image(&self, method: &Method, path: &str) -> bool {
+        if method != Method::GET {
+            return false;
+        }
+
+        match self {
+            HostSite::Avatar | HostSite::Image => true,
+            HostSite::Preview => {
+                let path = path.to_ascii_lowercase();
+                [".jpg", ".jpeg", ".png", ".gif", ".webp"]
+                    .iter()
+                    .any(|extension| path.ends_with(extension))
+            }
+            HostSite::Unknown => false,
+        }
+    }
+}
+
 impl From<Option<&str>> for HostSite {
     fn from(value: Option<&str>) -> Self {
         match value {
             Some(host) => match host {
                 "uc.javbus22.com" => HostSite::Avatar,
-                "www.javbus.com" => HostSite::Preview,
                 "forum.javcdn.cc" => HostSite::Image,
+                host if mirror::is_mirror(Some(host)) => HostSite::Preview,
                 _ => HostSite::Unknown,
             },
             None => HostSite::Unknown,
         }
     }
 }
+
+fn mirror_uri(uri: &Uri, host: &str) -> anyhow::Result<Uri> {
+    let scheme = uri.scheme_str().unwrap_or("https");
+    let path = uri
+        .path_and_query()
+        .map(|path| path.as_str())
+        .unwrap_or("/");
+
+    format!("{scheme}://{host}{path}")
+        .parse::<Uri>()
+        .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))
+}
+
+fn mirror_request(parts: &Parts, host: &str) -> anyhow::Result<Request<AsyncBody>> {
+    let mut request = Request::builder()
+        .method(parts.method.clone())
+        .uri(mirror_uri(&parts.uri, host)?)
+        .version(parts.version)
+        .body(AsyncBody::empty())
+        .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))?;
+    *request.headers_mut() = parts.headers.clone();
+    *request.extensions_mut() = parts.extensions.clone();
+
+    Ok(request)
+}
+
+#[derive(Clone, Copy)]
+pub struct MirrorsTried(pub usize);
+
+#[derive(Debug)]
+pub struct FailoverError {
+    tried: usize,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for FailoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (已尝试 {} 个镜像)", self.error, self.tried)
+    }
+}
+
+impl std::error::Error for FailoverError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+fn is_blocked(status: StatusCode) -> bool {
+    status == StatusCode::FORBIDDEN
+        || status == StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS
+        || status.is_server_error()
+}
+
+struct GlobalHttpClient(Arc<MultiHttpClient>);
+
+impl Global for GlobalHttpClient {}
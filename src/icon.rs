@@ -2,7 +2,7 @@ use gpui::SharedString;
 use gpui_component::{Icon, IconNamed};
 use strum::IntoStaticStr;
 
-#[derive(IntoStaticStr)]
+#[derive(IntoStaticStr, Clone, Copy, PartialEq, Eq)]
 #[strum(serialize_all = "snake_case")]
 pub enum IconName {
     House,
@@ -15,6 +15,19 @@ pub enum IconName {
     Plus,
     Minus,
     Eye,
+    Search,
+    Bookmark,
+    BookmarkCheck,
+    Trash,
+    Bell,
+    BellRing,
+    Download,
+    Images,
+    Settings,
+    Quote,
+    X,
+    RotateCcw,
+    Link,
 }
 
 impl IconNamed for IconName {
@@ -0,0 +1,257 @@
+use gpui::{
+    App, AppContext as _, Context, FocusHandle, Focusable, InteractiveElement as _, IntoElement,
+    KeyDownEvent, MouseButton, MouseDownEvent, MouseMoveEvent, ObjectFit, ParentElement as _,
+    Pixels, Point, Render, ScrollWheelEvent, SharedString, Styled as _, StyledImage as _, Window,
+    div, img, point, px, size,
+};
+use gpui_component::button::{Button, ButtonVariants as _};
+use gpui_component::label::Label;
+use gpui_component::{ActiveTheme as _, ContextModal as _, Disableable as _, Sizable as _};
+
+use crate::icon::IconName;
+
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 8.;
+const ZOOM_STEP: f32 = 1.25;
+const TOOLBAR_HEIGHT: Pixels = px(40.);
+
+pub struct ImageViewer {
+    images: Vec<SharedString>,
+    index: usize,
+    zoom: f32,
+    offset: Point<Pixels>,
+    drag_position: Option<Point<Pixels>>,
+    focus_handle: FocusHandle,
+}
+
+impl ImageViewer {
+    pub fn open(images: Vec<SharedString>, index: usize, window: &mut Window, cx: &mut App) {
+        if images.is_empty() {
+            return;
+        }
+
+        let viewer = cx.new(|cx| Self::new(images, index, cx));
+        let width = window.viewport_size().width * 0.9;
+        let modal_viewer = viewer.clone();
+        window.open_modal(cx, move |modal, _, _| {
+            modal.width(width).child(modal_viewer.clone())
+        });
+        viewer.read(cx).focus_handle.focus(window);
+    }
+
+    fn new(images: Vec<SharedString>, index: usize, cx: &mut Context<Self>) -> Self {
+        let index = index.min(images.len() - 1);
+
+        Self {
+            images,
+            index,
+            zoom: 1.,
+            offset: point(px(0.), px(0.)),
+            drag_position: None,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn step(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let index = if forward {
+            (self.index + 1).min(self.images.len() - 1)
+        } else {
+            self.index.saturating_sub(1)
+        };
+        if index == self.index {
+            return;
+        }
+
+        self.index = index;
+        self.fit(cx);
+    }
+
+    fn fit(&mut self, cx: &mut Context<Self>) {
+        self.zoom = 1.;
+        self.offset = point(px(0.), px(0.));
+        cx.notify();
+    }
+
+    fn zoom_by(&mut self, factor: f32, cx: &mut Context<Self>) {
+        let zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        if zoom == self.zoom {
+            return;
+        }
+
+        let scale = zoom / self.zoom;
+        self.offset = point(self.offset.x * scale, self.offset.y * scale);
+        self.zoom = zoom;
+        cx.notify();
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        match event.keystroke.key.as_str() {
+            "left" => self.step(false, cx),
+            "right" => self.step(true, cx),
+            "=" | "+" => self.zoom_by(ZOOM_STEP, cx),
+            "-" => self.zoom_by(1. / ZOOM_STEP, cx),
+            "0" => self.fit(cx),
+            _ => return,
+        }
+        cx.stop_propagation();
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(px(20.)).y;
+        if delta > px(0.) {
+            self.zoom_by(ZOOM_STEP, cx);
+        } else if delta < px(0.) {
+            self.zoom_by(1. / ZOOM_STEP, cx);
+        }
+    }
+
+    fn on_mouse_down(&mut self, event: &MouseDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        self.drag_position = Some(event.position);
+        cx.stop_propagation();
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(drag_position) = self.drag_position else {
+            return;
+        };
+        if event.pressed_button != Some(MouseButton::Left) {
+            self.drag_position = None;
+            return;
+        }
+
+        self.offset = self.offset + (event.position - drag_position);
+        self.drag_position = Some(event.position);
+        cx.notify();
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .h(TOOLBAR_HEIGHT)
+            .w_full()
+            .flex()
+            .items_center()
+            .justify_between()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(
+                        Button::new("ImageViewerPrevious")
+                            .icon(IconName::ChevronLeft)
+                            .ghost()
+                            .small()
+                            .cursor_pointer()
+                            .disabled(self.index == 0)
+                            .on_click(cx.listener(|this, _, _, cx| this.step(false, cx))),
+                    )
+                    .child(Label::new(format!(
+                        "{} / {}",
+                        self.index + 1,
+                        self.images.len()
+                    )))
+                    .child(
+                        Button::new("ImageViewerNext")
+                            .icon(IconName::ChevronRight)
+                            .ghost()
+                            .small()
+                            .cursor_pointer()
+                            .disabled(self.index + 1 >= self.images.len())
+                            .on_click(cx.listener(|this, _, _, cx| this.step(true, cx))),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(
+                        Button::new("ImageViewerZoomOut")
+                            .icon(IconName::Minus)
+                            .ghost()
+                            .small()
+                            .cursor_pointer()
+                            .on_click(
+                                cx.listener(|this, _, _, cx| this.zoom_by(1. / ZOOM_STEP, cx)),
+                            ),
+                    )
+                    .child(Label::new(format!("{:.0}%", self.zoom * 100.)))
+                    .child(
+                        Button::new("ImageViewerZoomIn")
+                            .icon(IconName::Plus)
+                            .ghost()
+                            .small()
+                            .cursor_pointer()
+                            .on_click(cx.listener(|this, _, _, cx| this.zoom_by(ZOOM_STEP, cx))),
+                    )
+                    .child(
+                        Button::new("ImageViewerFit")
+                            .label("适应窗口")
+                            .ghost()
+                            .small()
+                            .cursor_pointer()
+                            .on_click(cx.listener(|this, _, _, cx| this.fit(cx))),
+                    ),
+            )
+    }
+}
+
+impl Render for ImageViewer {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let viewport_size = window.viewport_size();
+        let stage_size = size(
+            viewport_size.width * 0.9 - px(48.),
+            viewport_size.height * 0.8 - TOOLBAR_HEIGHT,
+        );
+        let image_size = size(stage_size.width * self.zoom, stage_size.height * self.zoom);
+        let left = (stage_size.width - image_size.width) / 2. + self.offset.x;
+        let top = (stage_size.height - image_size.height) / 2. + self.offset.y;
+        let src = self.images[self.index].clone();
+        let toolbar = self.render_toolbar(cx);
+        let theme = cx.theme();
+
+        div()
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::on_key_down))
+            .flex()
+            .flex_col()
+            .child(toolbar)
+            .child(
+                div()
+                    .relative()
+                    .w(stage_size.width)
+                    .h(stage_size.height)
+                    .overflow_hidden()
+                    .rounded_md()
+                    .bg(theme.secondary)
+                    .cursor_grab()
+                    .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+                    .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+                    .on_mouse_move(cx.listener(Self::on_mouse_move))
+                    .on_mouse_up(
+                        MouseButton::Left,
+                        cx.listener(|this, _, _, _| this.drag_position = None),
+                    )
+                    .child(
+                        img(src)
+                            .absolute()
+                            .left(left)
+                            .top(top)
+                            .w(image_size.width)
+                            .h(image_size.height)
+                            .object_fit(ObjectFit::Contain),
+                    ),
+            )
+    }
+}
+
+impl Focusable for ImageViewer {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
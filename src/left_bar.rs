@@ -1,26 +1,37 @@
 use gpui::{
-    AnyElement, Context, FocusHandle, InteractiveElement, IntoElement, ParentElement as _, Pixels,
-    Render, Styled as _, Window, div, px,
+    AnyElement, AppContext as _, Context, ElementId, Entity, EventEmitter, FocusHandle,
+    InteractiveElement, IntoElement, ParentElement as _, Pixels, Render,
+    StatefulInteractiveElement as _, Styled as _, Window, div, prelude::FluentBuilder as _, px,
 };
 use gpui_component::{
-    ActiveTheme as _, Selectable as _,
+    ActiveTheme as _, ContextModal as _, Selectable as _,
     button::{Button, ButtonVariants as _},
 };
-use strum::IntoStaticStr;
 
+use super::board::{BoardInfo, BoardPicker, BoardPickerEvent};
 use super::icon::IconName;
 
 const LEFT_BAR_WIDTH: Pixels = px(50.);
 
 pub struct LeftBar {
+    boards: Vec<BoardInfo>,
+    board_picker: Entity<BoardPicker>,
     selected_item: LeftBarItem,
     focus_handle: FocusHandle,
 }
 
 impl LeftBar {
-    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+    pub fn new(boards: Vec<BoardInfo>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let selected_item = Self::first_item(&boards);
+        let pinned = boards.iter().map(|board| board.fid).collect();
+        let board_picker = cx.new(|cx| BoardPicker::new(pinned, window, cx));
+        cx.subscribe_in(&board_picker, window, Self::on_board_picker_event)
+            .detach();
+
         Self {
-            selected_item: LeftBarItem::Forum,
+            boards,
+            board_picker,
+            selected_item,
             focus_handle: cx.focus_handle(),
         }
     }
@@ -29,17 +40,91 @@ impl LeftBar {
         &self.selected_item
     }
 
-    fn render_item(&self, item: &LeftBarItem, cx: &mut Context<Self>) -> impl IntoElement {
-        let id: &'static str = item.into();
-        let button = Button::new(id)
-            .icon(item.icon())
+    fn first_item(boards: &[BoardInfo]) -> LeftBarItem {
+        boards
+            .first()
+            .map(|board| LeftBarItem::Board(board.fid))
+            .unwrap_or(LeftBarItem::Search)
+    }
+
+    fn on_board_picker_event(
+        &mut self,
+        _: &Entity<BoardPicker>,
+        event: &BoardPickerEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            BoardPickerEvent::Pin(board) => {
+                if self.boards.iter().any(|pinned| pinned.fid == board.fid) {
+                    return;
+                }
+                self.boards.push(board.clone());
+            }
+            BoardPickerEvent::Unpin(fid) => {
+                self.boards.retain(|board| board.fid != *fid);
+                if self.selected_item == LeftBarItem::Board(*fid) {
+                    self.selected_item = Self::first_item(&self.boards);
+                }
+            }
+            _ => return,
+        }
+
+        if let Err(error) = BoardInfo::save_pinned(&self.boards) {
+            window.push_notification(error.to_string(), cx);
+        }
+        cx.emit(LeftBarEvent::BoardsChanged(self.boards.clone()));
+        cx.notify();
+    }
+
+    fn open_board_picker(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        cx.stop_propagation();
+        self.board_picker.update(cx, |_, cx| {
+            cx.emit(BoardPickerEvent::Load);
+            cx.notify();
+        });
+        let board_picker = self.board_picker.clone();
+        window.open_modal(cx, move |modal, _, _| {
+            modal.title("选择版块").child(board_picker.clone())
+        });
+    }
+
+    fn render_item(&self, board: &BoardInfo, cx: &mut Context<Self>) -> impl IntoElement {
+        let item = LeftBarItem::Board(board.fid);
+        let button = Button::new(item.id())
+            .ghost()
+            .cursor_pointer()
+            .tooltip(board.name.clone())
+            .selected(self.selected_item == item)
+            .map(|button| match board.icon {
+                Some(icon) => button.icon(icon),
+                None => button.label(board.name.chars().take(1).collect::<String>()),
+            })
+            .on_click(cx.listener(move |left_bar, _, _, cx| left_bar.on_click(item.clone(), cx)));
+
+        div()
+            .w(LEFT_BAR_WIDTH)
+            .mt_2()
+            .flex()
+            .justify_center()
+            .items_center()
+            .child(button)
+    }
+
+    fn render_fixed_item(
+        &self,
+        item: LeftBarItem,
+        icon: IconName,
+        tooltip: &'static str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let button = Button::new(item.id())
+            .icon(icon)
             .ghost()
             .cursor_pointer()
-            .selected(&self.selected_item == item)
-            .on_click(cx.listener({
-                let item = item.clone();
-                move |left_bar, _, _, cx| left_bar.on_click(item.clone(), cx)
-            }));
+            .tooltip(tooltip)
+            .selected(self.selected_item == item)
+            .on_click(cx.listener(move |left_bar, _, _, cx| left_bar.on_click(item.clone(), cx)));
 
         div()
             .w(LEFT_BAR_WIDTH)
@@ -50,45 +135,111 @@ impl LeftBar {
             .child(button)
     }
 
+    fn render_board_picker_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let button = Button::new("BoardPicker")
+            .icon(IconName::Plus)
+            .ghost()
+            .cursor_pointer()
+            .tooltip("选择版块")
+            .on_click(
+                cx.listener(|left_bar, _, window, cx| left_bar.open_board_picker(window, cx)),
+            );
+
+        div()
+            .w(LEFT_BAR_WIDTH)
+            .my_2()
+            .flex()
+            .justify_center()
+            .items_center()
+            .child(button)
+    }
+
+    pub fn select(&mut self, item: LeftBarItem, cx: &mut Context<Self>) {
+        if self.selected_item == item {
+            return;
+        }
+
+        let previous = std::mem::replace(&mut self.selected_item, item);
+        cx.emit(LeftBarEvent::Selected(previous));
+        cx.notify();
+    }
+
+    pub fn restore(&mut self, item: LeftBarItem, cx: &mut Context<Self>) {
+        self.selected_item = item;
+        cx.notify();
+    }
+
     fn on_click(&mut self, item: LeftBarItem, cx: &mut Context<Self>) {
         cx.stop_propagation();
-        self.selected_item = item;
+        self.select(item, cx);
     }
 }
 
 impl Render for LeftBar {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let all_items = LeftBarItem::all_items()
+        let all_items = self
+            .boards
             .iter()
-            .map(|item| self.render_item(item, cx).into_any_element())
+            .map(|board| self.render_item(board, cx).into_any_element())
             .collect::<Vec<AnyElement>>();
+        let search_button =
+            self.render_fixed_item(LeftBarItem::Search, IconName::Search, "搜索", cx);
+        let bookmarks_button =
+            self.render_fixed_item(LeftBarItem::Bookmarks, IconName::Bookmark, "收藏", cx);
+        let watching_button =
+            self.render_fixed_item(LeftBarItem::Watching, IconName::Bell, "关注", cx);
+        let settings_button =
+            self.render_fixed_item(LeftBarItem::Settings, IconName::Settings, "设置", cx);
+        let board_picker_button = self.render_board_picker_button(cx);
         let theme = cx.theme();
 
         div()
             .track_focus(&self.focus_handle)
             .h_full()
             .w(LEFT_BAR_WIDTH)
+            .flex()
+            .flex_col()
             .border_r_1()
             .border_color(theme.border)
-            .children(all_items)
+            .child(
+                div()
+                    .id("LeftBarItems")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .children(all_items),
+            )
+            .child(search_button)
+            .child(bookmarks_button)
+            .child(watching_button)
+            .child(board_picker_button)
+            .child(settings_button)
     }
 }
 
-#[derive(PartialEq, Eq, IntoStaticStr, Clone)]
+pub enum LeftBarEvent {
+    BoardsChanged(Vec<BoardInfo>),
+    Selected(LeftBarItem),
+}
+
+impl EventEmitter<LeftBarEvent> for LeftBar {}
+
+#[derive(PartialEq, Eq, Clone)]
 pub enum LeftBarItem {
-    Forum,
-    Find,
+    Board(u32),
+    Search,
+    Bookmarks,
+    Watching,
+    Settings,
 }
 
 impl LeftBarItem {
-    fn all_items() -> &'static [Self] {
-        &[Self::Forum, Self::Find]
-    }
-
-    fn icon(&self) -> IconName {
+    fn id(&self) -> ElementId {
         match self {
-            LeftBarItem::Forum => IconName::House,
-            LeftBarItem::Find => IconName::BookMarked,
+            LeftBarItem::Board(fid) => ("Board", *fid as usize).into(),
+            LeftBarItem::Search => "Search".into(),
+            LeftBarItem::Bookmarks => "Bookmarks".into(),
+            LeftBarItem::Watching => "Watching".into(),
+            LeftBarItem::Settings => "Settings".into(),
         }
     }
 }
@@ -1,45 +1,432 @@
+pub mod account;
 pub mod assets;
+pub mod bookmark;
+pub mod history;
 pub mod http_client;
+pub mod link;
+pub mod navigation;
+pub mod reply;
 pub mod theme;
+pub mod watch;
 pub mod window;
 
-mod find;
-mod forum;
+mod board;
+mod cache;
+mod cookie;
+mod goto;
 mod icon;
+mod image_viewer;
 mod left_bar;
+mod login;
+mod mirror;
+mod page;
+mod proxy;
+mod retry;
+mod scroll;
+mod settings;
+mod store;
 
-use find::Find;
-use forum::Forum;
+use account::{Account, AccountEvent};
+use board::{Board, BoardInfo, Bookmarks, DetailTabs, Search, TabsEvent, ViewState, Watching};
+use goto::GotoForm;
 use gpui::{
-    AppContext as _, Context, Entity, FocusHandle, InteractiveElement, IntoElement,
-    ParentElement as _, Render, Styled as _, Window, div,
+    App, AppContext as _, Context, Entity, FocusHandle, InteractiveElement, IntoElement,
+    MouseButton, NavigationDirection, ParentElement as _, Render, Styled as _, Window, div,
 };
-use gpui_component::{ActiveTheme as _, Root, TITLE_BAR_HEIGHT, TitleBar};
-use left_bar::{LeftBar, LeftBarItem};
+use gpui_component::button::{Button, ButtonVariants as _};
+use gpui_component::label::Label;
+use gpui_component::notification::Notification;
+use gpui_component::{
+    ActiveTheme as _, ContextModal as _, Disableable as _, Root, Sizable as _, TITLE_BAR_HEIGHT,
+    TitleBar,
+};
+use icon::IconName;
+use left_bar::{LeftBar, LeftBarEvent, LeftBarItem};
+use link::ThreadLink;
+use login::LoginForm;
+use navigation::{GoBack, GoForward, Location, Navigation, OpenLink};
+use settings::Settings;
+use watch::{WatchEvent, WatchStore};
 
 pub struct Bustop {
     left_bar: Entity<LeftBar>,
-    forum: Entity<Forum>,
-    find: Entity<Find>,
+    boards: Vec<Entity<Board>>,
+    search: Entity<Search>,
+    bookmarks: Entity<Bookmarks>,
+    watching: Entity<Watching>,
+    settings: Entity<Settings>,
+    navigation: Navigation,
     focus_handle: FocusHandle,
 }
 
 impl Bustop {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let left_bar = cx.new(|cx| LeftBar::new(window, cx));
-        let forum = cx.new(|cx| Forum::new(window, cx));
-        let find = cx.new(|cx| Find::new(window, cx));
+        let board_infos = BoardInfo::load_pinned();
+        let left_bar = cx.new(|cx| LeftBar::new(board_infos.clone(), window, cx));
+        cx.subscribe_in(&left_bar, window, Self::on_left_bar_event)
+            .detach();
+        let search = cx.new(|cx| Search::new(board_infos.clone(), window, cx));
+        let bookmarks = cx.new(|cx| Bookmarks::new(window, cx));
+        let watching = cx.new(|cx| Watching::new(window, cx));
+        let settings = cx.new(|cx| Settings::new(window, cx));
+        cx.subscribe_in(&WatchStore::global(cx), window, Self::on_watch_event)
+            .detach();
+        cx.subscribe_in(&Account::global(cx), window, Self::on_account_event)
+            .detach();
+        cx.observe(&Account::global(cx), |_, _, cx| cx.notify())
+            .detach();
+        let boards = board_infos
+            .into_iter()
+            .map(|info| {
+                let fid = info.fid;
+                let board = cx.new(|cx| Board::new(info, window, cx));
+                let tabs = board.read(cx).tabs().clone();
+                Self::subscribe_tabs(LeftBarItem::Board(fid), &tabs, window, cx);
+                board
+            })
+            .collect();
+        for (item, tabs) in [
+            (LeftBarItem::Search, search.read(cx).tabs().clone()),
+            (LeftBarItem::Bookmarks, bookmarks.read(cx).tabs().clone()),
+            (LeftBarItem::Watching, watching.read(cx).tabs().clone()),
+        ] {
+            Self::subscribe_tabs(item, &tabs, window, cx);
+        }
 
         Self {
             left_bar,
-            forum,
-            find,
+            boards,
+            search,
+            bookmarks,
+            watching,
+            settings,
+            navigation: Navigation::default(),
             focus_handle: cx.focus_handle(),
         }
     }
 
-    fn titlebar(&self) -> TitleBar {
-        TitleBar::new()
+    fn subscribe_tabs(
+        item: LeftBarItem,
+        tabs: &Entity<DetailTabs>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.subscribe_in(tabs, window, move |this, _, event, _, cx| match event {
+            TabsEvent::Opened(previous) => {
+                let state = this.view_state(&item, cx).with_thread(previous.clone());
+                this.navigation.push(Location {
+                    item: item.clone(),
+                    state,
+                });
+                cx.notify();
+            }
+        })
+        .detach();
+    }
+
+    fn view_state(&self, item: &LeftBarItem, cx: &App) -> ViewState {
+        match item {
+            LeftBarItem::Board(fid) => self
+                .boards
+                .iter()
+                .find(|board| board.read(cx).info().fid == *fid)
+                .map(|board| board.read(cx).view_state(cx))
+                .unwrap_or_default(),
+            LeftBarItem::Search => self.search.read(cx).view_state(cx),
+            LeftBarItem::Bookmarks => self.bookmarks.read(cx).view_state(cx),
+            LeftBarItem::Watching => self.watching.read(cx).view_state(cx),
+            LeftBarItem::Settings => ViewState::default(),
+        }
+    }
+
+    fn current_location(&self, cx: &App) -> Location {
+        let item = self.left_bar.read(cx).selected_item().clone();
+        let state = self.view_state(&item, cx);
+
+        Location { item, state }
+    }
+
+    fn restore(&mut self, location: Location, window: &mut Window, cx: &mut Context<Self>) {
+        let Location { item, state } = location;
+        match item {
+            LeftBarItem::Board(fid) => {
+                let board = self
+                    .boards
+                    .iter()
+                    .find(|board| board.read(cx).info().fid == fid)
+                    .cloned();
+                if let Some(board) = board {
+                    board.update(cx, |board, cx| board.restore(state, window, cx));
+                }
+            }
+            LeftBarItem::Search => self
+                .search
+                .update(cx, |search, cx| search.restore(state, window, cx)),
+            LeftBarItem::Bookmarks => self
+                .bookmarks
+                .update(cx, |bookmarks, cx| bookmarks.restore(state, window, cx)),
+            LeftBarItem::Watching => self
+                .watching
+                .update(cx, |watching, cx| watching.restore(state, window, cx)),
+            LeftBarItem::Settings => {}
+        }
+        self.left_bar
+            .update(cx, |left_bar, cx| left_bar.restore(item, cx));
+        cx.notify();
+    }
+
+    fn go_back(&mut self, _: &GoBack, window: &mut Window, cx: &mut Context<Self>) {
+        let current = self.current_location(cx);
+        if let Some(location) = self.navigation.back(current) {
+            self.restore(location, window, cx);
+        }
+    }
+
+    fn go_forward(&mut self, _: &GoForward, window: &mut Window, cx: &mut Context<Self>) {
+        let current = self.current_location(cx);
+        if let Some(location) = self.navigation.forward(current) {
+            self.restore(location, window, cx);
+        }
+    }
+
+    pub fn handle_links(
+        &mut self,
+        links: smol::channel::Receiver<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.spawn_in(window, async move |this, cx| {
+            while let Ok(link) = links.recv().await {
+                let result = this.update_in(cx, |this, window, cx| {
+                    this.open_link(&link, window, cx);
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    pub fn open_link(&mut self, link: &str, window: &mut Window, cx: &mut Context<Self>) {
+        match ThreadLink::parse(link) {
+            Some(link) => self.open_thread(link, window, cx),
+            None => window.push_notification("无法识别的帖子链接", cx),
+        }
+    }
+
+    fn open_thread(&mut self, link: ThreadLink, window: &mut Window, cx: &mut Context<Self>) {
+        let item = self.left_bar.read(cx).selected_item().clone();
+        let opened = match item {
+            LeftBarItem::Board(fid) => self
+                .boards
+                .iter()
+                .find(|board| board.read(cx).info().fid == fid)
+                .map(|board| (board.read(cx).tabs().clone(), Some(fid))),
+            LeftBarItem::Bookmarks => Some((self.bookmarks.read(cx).tabs().clone(), None)),
+            LeftBarItem::Watching => Some((self.watching.read(cx).tabs().clone(), None)),
+            LeftBarItem::Search | LeftBarItem::Settings => None,
+        };
+        let (tabs, fid) = match opened {
+            Some(opened) => opened,
+            None => {
+                if item != LeftBarItem::Search {
+                    self.left_bar
+                        .update(cx, |left_bar, cx| left_bar.select(LeftBarItem::Search, cx));
+                }
+                (self.search.read(cx).tabs().clone(), None)
+            }
+        };
+        tabs.update(cx, |tabs, cx| {
+            tabs.open(link.href().into(), fid, link.page, window, cx);
+        });
+        window.activate_window();
+    }
+
+    fn open_goto(&mut self, _: &OpenLink, window: &mut Window, cx: &mut Context<Self>) {
+        GotoForm::open(cx.entity().downgrade(), window, cx);
+    }
+
+    fn on_left_bar_event(
+        &mut self,
+        _: &Entity<LeftBar>,
+        event: &LeftBarEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            LeftBarEvent::BoardsChanged(board_infos) => {
+                self.boards.retain(|board| {
+                    let fid = board.read(cx).info().fid;
+                    board_infos.iter().any(|info| info.fid == fid)
+                });
+                for info in board_infos {
+                    let exists = self
+                        .boards
+                        .iter()
+                        .any(|board| board.read(cx).info().fid == info.fid);
+                    if !exists {
+                        let item = LeftBarItem::Board(info.fid);
+                        let info = info.clone();
+                        let board = cx.new(|cx| Board::new(info, window, cx));
+                        let tabs = board.read(cx).tabs().clone();
+                        Self::subscribe_tabs(item, &tabs, window, cx);
+                        self.boards.push(board);
+                    }
+                }
+                self.navigation.retain(|item| match item {
+                    LeftBarItem::Board(fid) => board_infos.iter().any(|info| info.fid == *fid),
+                    _ => true,
+                });
+                self.search.update(cx, |search, cx| {
+                    search.set_boards(board_infos.clone(), cx);
+                });
+                cx.notify();
+            }
+            LeftBarEvent::Selected(previous) => {
+                let state = self.view_state(previous, cx);
+                self.navigation.push(Location {
+                    item: previous.clone(),
+                    state,
+                });
+                cx.notify();
+            }
+        }
+    }
+
+    fn on_watch_event(
+        &mut self,
+        _: &Entity<WatchStore>,
+        event: &WatchEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            WatchEvent::NewReplies {
+                title,
+                href,
+                fid,
+                page,
+                floor,
+                count,
+            } => {
+                let left_bar = self.left_bar.clone();
+                let watching = self.watching.clone();
+                let href = href.clone();
+                let fid = *fid;
+                let page = *page;
+                let floor = *floor;
+                let notification = Notification::new(format!("{count} 条新回复"))
+                    .title(title.clone())
+                    .on_click(move |_, _, cx| {
+                        left_bar.update(cx, |left_bar, cx| {
+                            left_bar.select(LeftBarItem::Watching, cx);
+                        });
+                        watching.update(cx, |watching, cx| {
+                            watching.open(href.clone(), fid, page, floor, cx);
+                        });
+                    });
+                window.push_notification(notification, cx);
+            }
+        }
+    }
+
+    fn on_account_event(
+        &mut self,
+        _: &Entity<Account>,
+        event: &AccountEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            AccountEvent::Expired => {
+                let notification = Notification::new("登录已过期, 点击重新登录")
+                    .on_click(|_, window, cx| LoginForm::open(window, cx));
+                window.push_notification(notification, cx);
+            }
+        }
+    }
+
+    fn logout(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let result = Account::global(cx).update(cx, |account, cx| account.logout(cx));
+        match result {
+            Ok(()) => window.push_notification("已退出登录", cx),
+            Err(error) => window.push_notification(error.to_string(), cx),
+        }
+    }
+
+    fn titlebar(&self, cx: &mut Context<Self>) -> TitleBar {
+        let account = match Account::global(cx).read(cx).username() {
+            Some(username) => div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .child(Label::new(username).text_sm())
+                .child(
+                    Button::new("TitleBarLogout")
+                        .label("退出")
+                        .ghost()
+                        .small()
+                        .cursor_pointer()
+                        .on_click(cx.listener(|this, _, window, cx| this.logout(window, cx))),
+                ),
+            None => div().child(
+                Button::new("TitleBarLogin")
+                    .label("登录")
+                    .ghost()
+                    .small()
+                    .cursor_pointer()
+                    .on_click(|_, window, cx| LoginForm::open(window, cx)),
+            ),
+        };
+
+        let history = div()
+            .flex()
+            .items_center()
+            .child(
+                Button::new("TitleBarBack")
+                    .icon(IconName::ChevronLeft)
+                    .ghost()
+                    .small()
+                    .cursor_pointer()
+                    .tooltip("后退")
+                    .disabled(!self.navigation.can_go_back())
+                    .on_click(cx.listener(|this, _, window, cx| this.go_back(&GoBack, window, cx))),
+            )
+            .child(
+                Button::new("TitleBarForward")
+                    .icon(IconName::ChevronRight)
+                    .ghost()
+                    .small()
+                    .cursor_pointer()
+                    .tooltip("前进")
+                    .disabled(!self.navigation.can_go_forward())
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.go_forward(&GoForward, window, cx)),
+                    ),
+            )
+            .child(
+                Button::new("TitleBarOpenLink")
+                    .icon(IconName::Link)
+                    .ghost()
+                    .small()
+                    .cursor_pointer()
+                    .tooltip("打开链接")
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.open_goto(&OpenLink, window, cx)),
+                    ),
+            );
+
+        TitleBar::new().child(
+            div()
+                .w_full()
+                .pr_2()
+                .flex()
+                .items_center()
+                .justify_between()
+                .child(history)
+                .child(account),
+        )
     }
 
     fn content(&self, cx: &mut Context<Self>) -> impl IntoElement {
@@ -57,15 +444,23 @@ impl Bustop {
         let container = div().w_full().h_full();
 
         match self.left_bar.read(cx).selected_item() {
-            LeftBarItem::Forum => container.child(self.forum.clone()),
-            LeftBarItem::Find => container.child(self.find.clone()),
+            LeftBarItem::Board(fid) => container.children(
+                self.boards
+                    .iter()
+                    .find(|board| board.read(cx).info().fid == *fid)
+                    .cloned(),
+            ),
+            LeftBarItem::Search => container.child(self.search.clone()),
+            LeftBarItem::Bookmarks => container.child(self.bookmarks.clone()),
+            LeftBarItem::Watching => container.child(self.watching.clone()),
+            LeftBarItem::Settings => container.child(self.settings.clone()),
         }
     }
 }
 
 impl Render for Bustop {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let titlebar = self.titlebar();
+        let titlebar = self.titlebar(cx);
         let content = self.content(cx);
         let total_height = window.bounds().size.height;
         let content_height = total_height - TITLE_BAR_HEIGHT;
@@ -75,6 +470,18 @@ impl Render for Bustop {
 
         div()
             .track_focus(&self.focus_handle)
+            .key_context(navigation::KEY_CONTEXT)
+            .on_action(cx.listener(Self::go_back))
+            .on_action(cx.listener(Self::go_forward))
+            .on_action(cx.listener(Self::open_goto))
+            .on_mouse_down(
+                MouseButton::Navigate(NavigationDirection::Back),
+                cx.listener(|this, _, window, cx| this.go_back(&GoBack, window, cx)),
+            )
+            .on_mouse_down(
+                MouseButton::Navigate(NavigationDirection::Forward),
+                cx.listener(|this, _, window, cx| this.go_forward(&GoForward, window, cx)),
+            )
             .w_full()
             .h_full()
             .bg(theme.background)
@@ -0,0 +1,150 @@
+use crate::mirror;
+
+pub const SCHEME: &str = "bustop://";
+
+pub fn thread_href(href: &str) -> String {
+    ThreadLink::parse(href)
+        .map(|link| link.href())
+        .unwrap_or_else(|| mirror::forum_url(href))
+}
+
+pub fn thread_key(href: &str) -> String {
+    ThreadLink::parse(href)
+        .map(|link| link.tid.to_string())
+        .unwrap_or_else(|| mirror::resource_key(href))
+}
+
+#[derive(Clone, Copy)]
+pub struct ThreadLink {
+    pub tid: u32,
+    pub page: u32,
+}
+
+impl ThreadLink {
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if let Some(rest) = input.strip_prefix(SCHEME) {
+            return Self::parse_scheme(rest);
+        }
+
+        let path = input
+            .split_once("://")
+            .map(|(_, rest)| rest.split_once('/').map(|(_, path)| path).unwrap_or(""))
+            .unwrap_or(input);
+        let path = path.split('#').next().unwrap_or(path);
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let file = path.rsplit('/').next().unwrap_or(path);
+
+        if let Some(link) = Self::parse_static(file) {
+            return Some(link);
+        }
+
+        let params = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .collect::<Vec<_>>();
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        };
+        if file != "forum.php" || param("mod") != Some("viewthread") {
+            return None;
+        }
+        let tid = param("tid")?.parse::<u32>().ok()?;
+        let page = param("page")
+            .and_then(|page| page.parse::<u32>().ok())
+            .unwrap_or(1);
+
+        Self::new(tid, page)
+    }
+
+    pub fn href(&self) -> String {
+        mirror::forum_url(&format!("forum.php?mod=viewthread&tid={}", self.tid))
+    }
+
+    fn new(tid: u32, page: u32) -> Option<Self> {
+        (tid > 0).then_some(Self {
+            tid,
+            page: page.max(1),
+        })
+    }
+
+    fn parse_scheme(rest: &str) -> Option<Self> {
+        let mut parts = rest.trim_end_matches('/').split('/');
+        if parts.next() != Some("thread") {
+            return None;
+        }
+        let tid = parts.next()?.parse::<u32>().ok()?;
+        let page = match parts.next() {
+            Some(page) => page.parse::<u32>().ok()?,
+            None => 1,
+        };
+
+        Self::new(tid, page)
+    }
+
+    fn parse_static(file: &str) -> Option<Self> {
+        let name = file.strip_prefix("thread-")?.strip_suffix(".html")?;
+        let mut parts = name.split('-');
+        let tid = parts.next()?.parse::<u32>().ok()?;
+        let page = parts.next()?.parse::<u32>().ok()?;
+
+        Self::new(tid, page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ThreadLink;
+
+    fn parse(input: &str) -> Option<(u32, u32)> {
+        ThreadLink::parse(input).map(|link| (link.tid, link.page))
+    }
+
+    #[test]
+    fn parses_static_thread_pages() {
+        assert_eq!(parse("thread-123-2-1.html"), Some((123, 2)));
+        assert_eq!(
+            parse("https://example.com/forum/thread-123-1-1.html"),
+            Some((123, 1))
+        );
+    }
+
+    #[test]
+    fn parses_viewthread_queries() {
+        assert_eq!(
+            parse("forum.php?mod=viewthread&tid=123&page=3"),
+            Some((123, 3))
+        );
+        assert_eq!(
+            parse("https://example.com/forum/forum.php?mod=viewthread&tid=123#pid1"),
+            Some((123, 1))
+        );
+        assert_eq!(
+            parse("forum.php?mod=viewthread&tid=123&page=x"),
+            Some((123, 1))
+        );
+    }
+
+    #[test]
+    fn parses_scheme_links() {
+        assert_eq!(parse("bustop://thread/123"), Some((123, 1)));
+        assert_eq!(parse("bustop://thread/123/4/"), Some((123, 4)));
+        assert_eq!(parse(" bustop://thread/123/0 "), Some((123, 1)));
+    }
+
+    #[test]
+    fn rejects_malformed_links() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("bustop://forum/123"), None);
+        assert_eq!(parse("bustop://thread/abc"), None);
+        assert_eq!(parse("bustop://thread/123/x"), None);
+        assert_eq!(parse("thread-0-1-1.html"), None);
+        assert_eq!(parse("thread-123.html"), None);
+        assert_eq!(parse("forum.php?mod=forumdisplay&fid=2"), None);
+        assert_eq!(parse("forum.php?mod=viewthread&tid="), None);
+        assert_eq!(parse("index.php?mod=viewthread&tid=123"), None);
+    }
+}
@@ -0,0 +1,103 @@
+use gpui::{
+    App, AppContext as _, Context, Entity, FocusHandle, InteractiveElement as _, IntoElement,
+    ParentElement as _, Render, Styled as _, Window, div, px,
+};
+use gpui_component::button::Button;
+use gpui_component::input::{InputEvent, InputState, TextInput};
+use gpui_component::{ContextModal as _, Disableable as _, Sizable as _};
+
+use crate::account::Account;
+
+pub struct LoginForm {
+    username_state: Entity<InputState>,
+    password_state: Entity<InputState>,
+    focus_handle: FocusHandle,
+}
+
+impl LoginForm {
+    pub fn open(window: &mut Window, cx: &mut App) {
+        let form = cx.new(|cx| Self::new(window, cx));
+        window.open_modal(cx, move |modal, _, _| {
+            modal.title("登录").width(px(360.)).child(form.clone())
+        });
+    }
+
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let username_state = cx.new(|cx| InputState::new(window, cx).placeholder("用户名"));
+        let password_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder("密码").masked(true));
+        cx.subscribe_in(&password_state, window, Self::on_input_event)
+            .detach();
+        cx.observe(&Account::global(cx), |_, _, cx| cx.notify())
+            .detach();
+
+        Self {
+            username_state,
+            password_state,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::PressEnter { secondary: _ } = event {
+            self.submit(window, cx);
+        }
+    }
+
+    fn submit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let account = Account::global(cx);
+        if account.read(cx).is_logging_in() {
+            return;
+        }
+
+        let username = self.username_state.read(cx).value().trim().to_string();
+        let password = self.password_state.read(cx).value().to_string();
+        let task = account.update(cx, |account, cx| account.login(username, password, cx));
+        cx.spawn_in(window, async move |_, cx| {
+            let result = task.await;
+            cx.update(|window, cx| match result {
+                Ok(()) => {
+                    window.close_modal(cx);
+                    window.push_notification("登录成功", cx);
+                }
+                Err(error) => window.push_notification(error.to_string(), cx),
+            })
+            .ok();
+        })
+        .detach();
+    }
+}
+
+impl Render for LoginForm {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_logging_in = Account::global(cx).read(cx).is_logging_in();
+
+        div()
+            .track_focus(&self.focus_handle)
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(TextInput::new(&self.username_state))
+            .child(TextInput::new(&self.password_state))
+            .child(
+                div().flex().justify_end().child(
+                    Button::new("LoginSubmit")
+                        .label(if is_logging_in {
+                            "登录中..."
+                        } else {
+                            "登录"
+                        })
+                        .small()
+                        .cursor_pointer()
+                        .disabled(is_logging_in)
+                        .on_click(cx.listener(|this, _, window, cx| this.submit(window, cx))),
+                ),
+            )
+    }
+}
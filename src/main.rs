@@ -1,19 +1,41 @@
 use bustop::assets::Assets;
-use bustop::{Bustop, http_client, theme, window};
+use bustop::{
+    Bustop, account, bookmark, history, http_client, link, navigation, theme, watch, window,
+};
 use gpui::{App, AppContext as _, Application};
 use gpui_component::theme as gpui_theme;
 use gpui_component::{Root, input};
 
 fn main() {
-    Application::new().with_assets(Assets).run(|cx: &mut App| {
+    let (links_tx, links_rx) = smol::channel::unbounded::<String>();
+    std::env::args()
+        .skip(1)
+        .filter(|arg| arg.starts_with(link::SCHEME))
+        .for_each(|arg| {
+            links_tx.try_send(arg).ok();
+        });
+
+    let app = Application::new().with_assets(Assets);
+    app.on_open_urls(move |urls| {
+        for url in urls {
+            links_tx.try_send(url).ok();
+        }
+    });
+    app.run(move |cx: &mut App| {
         gpui_theme::init(cx);
         http_client::init(cx);
+        account::init(cx);
+        bookmark::init(cx);
+        history::init(cx);
+        watch::init(cx);
         input::init(cx);
+        navigation::init(cx);
 
         let window_options = window::window_options(cx);
         cx.open_window(window_options, |window, cx| {
             theme::sync_with_system(window);
             let bustop = cx.new(|cx| Bustop::new(window, cx));
+            bustop.update(cx, |bustop, cx| bustop.handle_links(links_rx, window, cx));
 
             cx.new(|cx| Root::new(bustop.into(), window, cx))
         })
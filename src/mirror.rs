@@ -0,0 +1,153 @@
+use std::sync::{LazyLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::store;
+
+const MIRRORS: &str = "mirrors";
+const DEFAULT_MIRROR: &str = "www.javbus.com";
+
+static STATE: LazyLock<RwLock<MirrorState>> = LazyLock::new(|| {
+    let state = store::load::<MirrorState>(MIRRORS)
+        .filter(|state| !state.domains.is_empty())
+        .unwrap_or_default();
+
+    RwLock::new(state)
+});
+
+#[derive(Clone, Serialize, Deserialize)]
+struct MirrorState {
+    domains: Vec<String>,
+    active: usize,
+}
+
+impl Default for MirrorState {
+    fn default() -> Self {
+        Self {
+            domains: vec![DEFAULT_MIRROR.to_string()],
+            active: 0,
+        }
+    }
+}
+
+impl MirrorState {
+    fn active_host(&self) -> &str {
+        self.domains
+            .get(self.active)
+            .or_else(|| self.domains.first())
+            .map(|domain| domain.as_str())
+            .unwrap_or(DEFAULT_MIRROR)
+    }
+}
+
+pub fn active_host() -> String {
+    STATE
+        .read()
+        .map(|state| state.active_host().to_string())
+        .unwrap_or_else(|_| DEFAULT_MIRROR.to_string())
+}
+
+pub fn base_url() -> String {
+    format!("https://{}", active_host())
+}
+
+pub fn forum_url(path: &str) -> String {
+    format!("{}/forum/{}", base_url(), path.trim_start_matches('/'))
+}
+
+pub fn resource_key(url: &str) -> String {
+    let Some((_, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    let (authority, path) = match rest.find(['/', '?', '#']) {
+        Some(ix) => (&rest[..ix], &rest[ix..]),
+        None => (rest, ""),
+    };
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let host = host.split(':').next().unwrap_or(host);
+    if is_mirror(Some(host)) || path.starts_with("/forum/") {
+        return path.to_string();
+    }
+
+    url.to_string()
+}
+
+pub fn domains() -> Vec<String> {
+    STATE
+        .read()
+        .map(|state| state.domains.clone())
+        .unwrap_or_default()
+}
+
+pub fn is_mirror(host: Option<&str>) -> bool {
+    let Some(host) = host else {
+        return false;
+    };
+    if host.eq_ignore_ascii_case(DEFAULT_MIRROR) {
+        return true;
+    }
+
+    STATE
+        .read()
+        .map(|state| {
+            state
+                .domains
+                .iter()
+                .any(|domain| domain.eq_ignore_ascii_case(host))
+        })
+        .unwrap_or(false)
+}
+
+pub fn failover_hosts() -> Vec<String> {
+    let Ok(state) = STATE.read() else {
+        return vec![DEFAULT_MIRROR.to_string()];
+    };
+    let active = state.active.min(state.domains.len().saturating_sub(1));
+
+    state.domains[active..]
+        .iter()
+        .chain(state.domains[..active].iter())
+        .cloned()
+        .collect()
+}
+
+pub fn set_active(host: &str) {
+    let Ok(mut state) = STATE.write() else {
+        return;
+    };
+    let Some(active) = state.domains.iter().position(|domain| domain == host) else {
+        return;
+    };
+    if state.active == active {
+        return;
+    }
+
+    state.active = active;
+    store::save(MIRRORS, &*state).ok();
+}
+
+pub fn set_domains(domains: Vec<String>) -> anyhow::Result<()> {
+    let domains = domains
+        .into_iter()
+        .map(|domain| {
+            domain
+                .trim()
+                .trim_start_matches("https://")
+                .trim_start_matches("http://")
+                .trim_end_matches('/')
+                .to_ascii_lowercase()
+        })
+        .filter(|domain| !domain.is_empty())
+        .collect::<Vec<_>>();
+    anyhow::ensure!(!domains.is_empty(), "请至少填写一个镜像域名");
+
+    let mut state = STATE.write().map_err(|_| anyhow::anyhow!("更新镜像失败"))?;
+    let active_host = state.active_host().to_string();
+    state.active = domains
+        .iter()
+        .position(|domain| *domain == active_host)
+        .unwrap_or(0);
+    state.domains = domains;
+
+    store::save(MIRRORS, &*state)
+}
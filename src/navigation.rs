@@ -0,0 +1,70 @@
+use gpui::{App, KeyBinding, actions};
+
+use crate::board::ViewState;
+use crate::left_bar::LeftBarItem;
+
+pub const KEY_CONTEXT: &str = "Bustop";
+const MAX_HISTORY: usize = 100;
+#[cfg(target_os = "macos")]
+const OPEN_LINK_KEY: &str = "cmd-l";
+#[cfg(not(target_os = "macos"))]
+const OPEN_LINK_KEY: &str = "ctrl-l";
+
+actions!(bustop, [GoBack, GoForward, OpenLink]);
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("alt-left", GoBack, Some(KEY_CONTEXT)),
+        KeyBinding::new("alt-right", GoForward, Some(KEY_CONTEXT)),
+        KeyBinding::new(OPEN_LINK_KEY, OpenLink, Some(KEY_CONTEXT)),
+    ]);
+}
+
+#[derive(Clone)]
+pub(crate) struct Location {
+    pub item: LeftBarItem,
+    pub state: ViewState,
+}
+
+#[derive(Default)]
+pub(crate) struct Navigation {
+    back: Vec<Location>,
+    forward: Vec<Location>,
+}
+
+impl Navigation {
+    pub fn push(&mut self, location: Location) {
+        self.back.push(location);
+        if self.back.len() > MAX_HISTORY {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    pub fn back(&mut self, current: Location) -> Option<Location> {
+        let location = self.back.pop()?;
+        self.forward.push(current);
+
+        Some(location)
+    }
+
+    pub fn forward(&mut self, current: Location) -> Option<Location> {
+        let location = self.forward.pop()?;
+        self.back.push(current);
+
+        Some(location)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    pub fn retain(&mut self, f: impl Fn(&LeftBarItem) -> bool) {
+        self.back.retain(|location| f(&location.item));
+        self.forward.retain(|location| f(&location.item));
+    }
+}
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use http_client::http::{HeaderMap, StatusCode, header};
+use http_client::{AsyncBody, HttpClient, Request};
+use scraper::{Html, Selector};
+use smol::io::AsyncReadExt as _;
+
+use crate::cache;
+use crate::retry;
+
+const COOKIE: &str = "existmag=mag";
+const CONTENT: &str =
+    "#postlist, #threadlisttableid, #threadlist, table.fl_tb, form[name=\"login\"]";
+const CHALLENGE: &str = "#challenge-form, #challenge-running, #cf-wrapper, script[src*=\"/cdn-cgi/challenge-platform/\"]";
+const CHALLENGE_TITLE: &str = "Just a moment";
+const AGE_GATE: &str =
+    "#age-verify, .age-verify, #ageverify, .ageverify, #driver-verify, .driver-verify";
+const AGE_GATE_MARKERS: [&str; 2] = ["已滿18歲", "已满18岁"];
+const LOGIN_WALL: &str = "#messagelogin";
+const LOGIN_WALL_MARKERS: [&str; 4] = ["尚未登錄", "尚未登录", "請先登錄", "请先登录"];
+const MESSAGE: &str = "#messagetext";
+
+#[derive(Clone)]
+pub struct AgeCookie(pub String);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PageKind {
+    Content,
+    Challenge,
+    AgeGate,
+    LoginWall,
+}
+
+impl PageKind {
+    pub fn classify(headers: &HeaderMap, text: &str) -> Self {
+        let is_challenge = headers
+            .get("cf-mitigated")
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.eq_ignore_ascii_case("challenge"));
+        if is_challenge {
+            return Self::Challenge;
+        }
+
+        let html = Html::parse_document(text);
+        if has_match(&html, CONTENT) {
+            return Self::Content;
+        }
+        let title = select_text(&html, "title");
+        let message = select_text(&html, MESSAGE);
+        if title.contains(CHALLENGE_TITLE) || has_match(&html, CHALLENGE) {
+            return Self::Challenge;
+        }
+        if has_match(&html, AGE_GATE)
+            || contains_any(&title, &AGE_GATE_MARKERS)
+            || contains_any(&message, &AGE_GATE_MARKERS)
+        {
+            return Self::AgeGate;
+        }
+        if has_match(&html, LOGIN_WALL) || contains_any(&message, &LOGIN_WALL_MARKERS) {
+            return Self::LoginWall;
+        }
+
+        Self::Content
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        match self {
+            Self::Content => Ok(()),
+            Self::Challenge => anyhow::bail!("遇到 Cloudflare 验证, 请稍后再试或切换镜像"),
+            Self::AgeGate => anyhow::bail!("年龄确认失败"),
+            Self::LoginWall => anyhow::bail!("需要登录才能查看"),
+        }
+    }
+}
+
+fn contains_any(text: &str, markers: &[&str]) -> bool {
+    markers.iter().any(|marker| text.contains(marker))
+}
+
+fn has_match(html: &Html, selector: &str) -> bool {
+    Selector::parse(selector)
+        .map(|selector| html.select(&selector).next().is_some())
+        .unwrap_or(false)
+}
+
+fn select_text(html: &Html, selector: &str) -> String {
+    Selector::parse(selector)
+        .ok()
+        .and_then(|selector| {
+            html.select(&selector)
+                .next()
+                .map(|element| element.text().collect())
+        })
+        .unwrap_or_default()
+}
+
+fn gate_cookie(headers: &HeaderMap, text: &str) -> Option<String> {
+    let cookies = headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .flat_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';').next())
+        .map(|cookie| cookie.trim())
+        .filter(|cookie| cookie.contains('='))
+        .collect::<Vec<_>>();
+    if !cookies.is_empty() {
+        return Some(cookies.join("; "));
+    }
+
+    let (_, rest) = text.split_once("document.cookie")?;
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let (value, _) = rest[1..].split_once(quote)?;
+    let cookie = value.split(';').next()?.trim();
+
+    cookie.contains('=').then(|| cookie.to_string())
+}
+
+pub async fn fetch(
+    http_client: &Arc<dyn HttpClient>,
+    request: impl Fn(&str) -> anyhow::Result<Request<AsyncBody>>,
+) -> anyhow::Result<(String, Option<NaiveDateTime>)> {
+    let mut page = fetch_once(http_client, &request, COOKIE, None).await?;
+    if let Some(gate_cookie) = page.gate_cookie.take() {
+        let cookie = format!("{COOKIE}; {gate_cookie}");
+        let age_cookie = AgeCookie(gate_cookie);
+        page = fetch_once(http_client, &request, &cookie, Some(age_cookie)).await?;
+    }
+    page.kind.check()?;
+    anyhow::ensure!(page.status.is_success(), "加载页面失败 - {}", page.status);
+
+    Ok((page.text, page.cached_at))
+}
+
+struct Page {
+    kind: PageKind,
+    gate_cookie: Option<String>,
+    status: StatusCode,
+    text: String,
+    cached_at: Option<NaiveDateTime>,
+}
+
+async fn fetch_once(
+    http_client: &Arc<dyn HttpClient>,
+    request: &impl Fn(&str) -> anyhow::Result<Request<AsyncBody>>,
+    cookie: &str,
+    age_cookie: Option<AgeCookie>,
+) -> anyhow::Result<Page> {
+    let response = retry::send(http_client, || {
+        let mut request = request(cookie)?;
+        if let Some(ref age_cookie) = age_cookie {
+            request.extensions_mut().insert(age_cookie.clone());
+        }
+
+        Ok(request)
+    })
+    .await?;
+    let cached_at = cache::cached_at(&response);
+    let (parts, mut body) = response.into_parts();
+
+    let mut text = String::new();
+    body.read_to_string(&mut text)
+        .await
+        .map_err(|error| anyhow::anyhow!("读取内容失败 - {error}"))?;
+
+    let kind = parts
+        .extensions
+        .get::<PageKind>()
+        .copied()
+        .unwrap_or_else(|| PageKind::classify(&parts.headers, &text));
+    let gate_cookie = match kind {
+        PageKind::AgeGate => gate_cookie(&parts.headers, &text),
+        _ => None,
+    };
+
+    Ok(Page {
+        kind,
+        gate_cookie,
+        status: parts.status,
+        text,
+        cached_at,
+    })
+}
+
+pub fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+pub fn formhash(text: &str) -> Option<String> {
+    let selector = Selector::parse("input[name=\"formhash\"]")
+        .expect("Failed to parse selector: input[name=\"formhash\"]");
+
+    Html::parse_document(text)
+        .select(&selector)
+        .next()
+        .and_then(|input| input.attr("value"))
+        .map(String::from)
+}
+
+pub fn ajax_error(text: &str) -> Option<String> {
+    let (_, rest) = text.split_once("errorhandle_")?;
+    let (_, rest) = rest.split_once("('")?;
+    let (message, _) = rest.split_once('\'')?;
+    let message = message.trim();
+
+    (!message.is_empty()).then(|| message.to_string())
+}
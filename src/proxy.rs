@@ -0,0 +1,92 @@
+use http_client::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::store;
+
+const PROXY: &str = "proxy";
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ProxyKind {
+    #[default]
+    Http,
+    Https,
+    Socks5,
+}
+
+impl ProxyKind {
+    pub fn all() -> [Self; 3] {
+        [Self::Http, Self::Https, Self::Socks5]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Http => "HTTP",
+            Self::Https => "HTTPS",
+            Self::Socks5 => "SOCKS5",
+        }
+    }
+
+    fn scheme(&self) -> &'static str {
+        match self {
+            Self::Http => "http",
+            Self::Https => "https",
+            Self::Socks5 => "socks5h",
+        }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxySettings {
+    pub enabled: bool,
+    pub kind: ProxyKind,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub bypass: Vec<String>,
+}
+
+impl ProxySettings {
+    pub fn load() -> Self {
+        store::load(PROXY).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        store::save_private(PROXY, self)
+    }
+
+    pub fn url(&self) -> anyhow::Result<Url> {
+        let host = self.host.trim();
+        anyhow::ensure!(!host.is_empty(), "请输入代理地址");
+        anyhow::ensure!(self.port != 0, "请输入代理端口");
+
+        let mut url = Url::parse(&format!("{}://{host}:{}", self.kind.scheme(), self.port))
+            .map_err(|error| anyhow::anyhow!("代理地址无效 - {error}"))?;
+        if !self.username.is_empty() {
+            url.set_username(&self.username)
+                .map_err(|_| anyhow::anyhow!("代理用户名无效"))?;
+            url.set_password(Some(&self.password))
+                .map_err(|_| anyhow::anyhow!("代理密码无效"))?;
+        }
+
+        Ok(url)
+    }
+
+    pub fn bypasses(&self, host: Option<&str>) -> bool {
+        let Some(host) = host else {
+            return false;
+        };
+
+        self.bypass.iter().any(|rule| {
+            let rule = rule.trim().to_ascii_lowercase();
+            if rule == "*" {
+                return true;
+            }
+
+            let domain = rule.trim_start_matches('*').trim_start_matches('.');
+            !domain.is_empty()
+                && (host.eq_ignore_ascii_case(domain)
+                    || host.to_ascii_lowercase().ends_with(&format!(".{domain}")))
+        })
+    }
+}
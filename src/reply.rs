@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use chrono::{Local, NaiveDateTime};
+use http_client::{AsyncBody, HttpClient, Request};
+use scraper::{Html, Selector};
+use smol::io::AsyncReadExt as _;
+
+use crate::page::{self, encode};
+
+const QUOTE_LENGTH: usize = 100;
+
+pub struct ReplyForm {
+    pub action: String,
+    pub formhash: String,
+}
+
+pub async fn fetch_form(
+    http_client: &Arc<dyn HttpClient>,
+    href: &str,
+) -> anyhow::Result<ReplyForm> {
+    let (text, _) = page::fetch(http_client, |cookie| {
+        Request::builder()
+            .method("GET")
+            .uri(href)
+            .header("Cookie", cookie)
+            .header("Cache-Control", "no-cache")
+            .header("Accept-Language", "zh-CN,zh-Hans;q=0.9")
+            .body(AsyncBody::empty())
+            .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))
+    })
+    .await?;
+
+    let selector =
+        Selector::parse("form#fastpostform").expect("Failed to parse selector: form#fastpostform");
+    let action = Html::parse_document(&text)
+        .select(&selector)
+        .next()
+        .and_then(|form| form.attr("action"))
+        .map(|action| resolve(href, action))
+        .ok_or_else(|| anyhow::anyhow!("没有找到回复表单, 请确认已登录且有回复权限"))?;
+    let formhash = page::formhash(&text).ok_or_else(|| anyhow::anyhow!("解析回复表单失败"))?;
+
+    Ok(ReplyForm { action, formhash })
+}
+
+pub async fn post(
+    http_client: &Arc<dyn HttpClient>,
+    form: &ReplyForm,
+    message: &str,
+) -> anyhow::Result<Option<u32>> {
+    anyhow::ensure!(!message.trim().is_empty(), "请输入回复内容");
+
+    let body = format!(
+        "message={}&posttime={}&formhash={}&usesig=1&subject=",
+        encode(message),
+        Local::now().timestamp(),
+        encode(&form.formhash),
+    );
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("{}&inajax=1", form.action))
+        .header("Cookie", "existmag=mag")
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .header("Accept-Language", "zh-CN,zh-Hans;q=0.9")
+        .body(AsyncBody::from(body))
+        .map_err(|error| anyhow::anyhow!("构建请求失败 - {error}"))?;
+    let response = http_client.send(request).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "回复失败 - {}",
+        response.status()
+    );
+
+    let mut text = String::new();
+    let mut body = response.into_body();
+    body.read_to_string(&mut text)
+        .await
+        .map_err(|error| anyhow::anyhow!("读取内容失败 - {error}"))?;
+    if let Some((_, rest)) = text.split_once("succeedhandle_") {
+        let page = rest
+            .split(&['?', '&', '\'', '#'])
+            .find_map(|pair| pair.strip_prefix("page="))
+            .and_then(|page| page.parse::<u32>().ok());
+
+        return Ok(page);
+    }
+
+    match page::ajax_error(&text) {
+        Some(message) => anyhow::bail!("回复失败 - {message}"),
+        None => anyhow::bail!("回复失败"),
+    }
+}
+
+pub fn quote(author_name: &str, published_at: NaiveDateTime, text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut excerpt = text.chars().take(QUOTE_LENGTH).collect::<String>();
+    if text.chars().count() > QUOTE_LENGTH {
+        excerpt.push_str(" ...");
+    }
+
+    format!(
+        "[quote][size=2][color=#999999]{author_name} 发表于 {}[/color][/size]\n{excerpt}[/quote]\n",
+        published_at.format("%Y-%m-%d %H:%M")
+    )
+}
+
+fn resolve(href: &str, action: &str) -> String {
+    if action.starts_with("http://") || action.starts_with("https://") {
+        return action.to_string();
+    }
+
+    let base = href.split('?').next().unwrap_or(href);
+    let base = base.rsplit_once('/').map(|(base, _)| base).unwrap_or(base);
+
+    format!("{base}/{}", action.trim_start_matches('/'))
+}
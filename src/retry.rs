@@ -0,0 +1,138 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher as _, Hasher as _};
+use std::io;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+
+use http_client::{AsyncBody, HttpClient, Request, Response};
+use serde::{Deserialize, Serialize};
+
+use crate::http_client::{FailoverError, MirrorsTried};
+use crate::store;
+
+const RETRY_SETTINGS: &str = "retry_settings";
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const BASE_DELAY_MS: u64 = 500;
+const MAX_DELAY_MS: u64 = 8000;
+
+static SETTINGS: LazyLock<RwLock<RetrySettings>> =
+    LazyLock::new(|| RwLock::new(store::load(RETRY_SETTINGS).unwrap_or_default()));
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct RetrySettings {
+    pub max_attempts: u32,
+    pub timeout_secs: u64,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+        }
+    }
+}
+
+pub fn settings() -> RetrySettings {
+    SETTINGS
+        .read()
+        .map(|settings| *settings)
+        .unwrap_or_default()
+}
+
+pub fn set_max_attempts(max_attempts: u32) -> anyhow::Result<()> {
+    update(|settings| settings.max_attempts = max_attempts.max(1))
+}
+
+pub fn set_timeout_secs(timeout_secs: u64) -> anyhow::Result<()> {
+    update(|settings| settings.timeout_secs = timeout_secs.max(1))
+}
+
+fn update(f: impl FnOnce(&mut RetrySettings)) -> anyhow::Result<()> {
+    let mut settings = SETTINGS
+        .write()
+        .map_err(|_| anyhow::anyhow!("更新设置失败"))?;
+    f(&mut settings);
+
+    store::save(RETRY_SETTINGS, &*settings)
+}
+
+pub async fn send(
+    http_client: &Arc<dyn HttpClient>,
+    request: impl Fn() -> anyhow::Result<Request<AsyncBody>>,
+) -> anyhow::Result<Response<AsyncBody>> {
+    let settings = settings();
+    let mut attempt = 1;
+    loop {
+        let result =
+            smol::future::or(http_client.send(request()?), timeout(settings.timeout_secs)).await;
+        let error = match result {
+            Ok(response) if response.extensions().get::<MirrorsTried>().is_some() => {
+                return Ok(response);
+            }
+            Ok(response) if !is_transient(&response) => return Ok(response),
+            Ok(response) => anyhow::anyhow!("服务器错误 - {}", response.status()),
+            Err(error) if error.is::<FailoverError>() => return Err(error),
+            Err(error) if is_retryable(&error) => error,
+            Err(error) => return Err(error),
+        };
+        if attempt >= settings.max_attempts {
+            anyhow::bail!("{error} (已尝试 {attempt} 次)");
+        }
+
+        smol::Timer::after(backoff(attempt)).await;
+        attempt += 1;
+    }
+}
+
+fn is_transient(response: &Response<AsyncBody>) -> bool {
+    response.status().is_server_error() && !response.headers().contains_key("cf-mitigated")
+}
+
+fn is_retryable(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause.is::<Timeout>()
+            || cause.downcast_ref::<io::Error>().is_some_and(|error| {
+                matches!(
+                    error.kind(),
+                    io::ErrorKind::ConnectionRefused
+                        | io::ErrorKind::ConnectionReset
+                        | io::ErrorKind::ConnectionAborted
+                        | io::ErrorKind::NotConnected
+                        | io::ErrorKind::BrokenPipe
+                        | io::ErrorKind::TimedOut
+                        | io::ErrorKind::UnexpectedEof
+                        | io::ErrorKind::HostUnreachable
+                        | io::ErrorKind::NetworkUnreachable
+                )
+            })
+    })
+}
+
+#[derive(Debug)]
+struct Timeout(u64);
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "请求超时 - {} 秒", self.0)
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+async fn timeout(secs: u64) -> anyhow::Result<Response<AsyncBody>> {
+    smol::Timer::after(Duration::from_secs(secs)).await;
+
+    Err(Timeout(secs).into())
+}
+
+fn backoff(attempt: u32) -> Duration {
+    let delay = BASE_DELAY_MS
+        .saturating_mul(1 << (attempt - 1).min(16))
+        .min(MAX_DELAY_MS);
+    let jitter = RandomState::new().build_hasher().finish() % (delay / 2 + 1);
+
+    Duration::from_millis(delay / 2 + jitter)
+}
@@ -0,0 +1,31 @@
+use std::sync::{LazyLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::store;
+
+const SCROLL_SETTINGS: &str = "scroll_settings";
+
+static SETTINGS: LazyLock<RwLock<ScrollSettings>> =
+    LazyLock::new(|| RwLock::new(store::load(SCROLL_SETTINGS).unwrap_or_default()));
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ScrollSettings {
+    pub infinite: bool,
+}
+
+pub fn is_infinite() -> bool {
+    SETTINGS
+        .read()
+        .map(|settings| settings.infinite)
+        .unwrap_or_default()
+}
+
+pub fn set_infinite(infinite: bool) -> anyhow::Result<()> {
+    let mut settings = SETTINGS
+        .write()
+        .map_err(|_| anyhow::anyhow!("更新设置失败"))?;
+    settings.infinite = infinite;
+
+    store::save(SCROLL_SETTINGS, &*settings)
+}
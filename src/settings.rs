@@ -0,0 +1,456 @@
+use gpui::{
+    AppContext as _, Context, Div, Entity, FocusHandle, InteractiveElement, IntoElement,
+    ParentElement as _, Render, SharedString, StatefulInteractiveElement as _, Styled as _, Window,
+    div,
+};
+use gpui_component::button::{Button, ButtonVariants as _};
+use gpui_component::checkbox::Checkbox;
+use gpui_component::input::{InputState, TextInput};
+use gpui_component::label::Label;
+use gpui_component::{
+    ActiveTheme as _, ContextModal as _, Selectable as _, Sizable as _, StyledExt as _,
+};
+
+use crate::cache::{ImageCache, PageCache};
+use crate::http_client::MultiHttpClient;
+use crate::mirror;
+use crate::proxy::{ProxyKind, ProxySettings};
+use crate::retry;
+use crate::scroll;
+
+const FRESHNESS_MINUTES: [u64; 4] = [1, 5, 15, 60];
+const IMAGE_CACHE_MB: [u64; 4] = [100, 200, 500, 1000];
+const MAX_ATTEMPTS: [u32; 4] = [1, 3, 5, 8];
+const TIMEOUT_SECS: [u64; 4] = [10, 30, 60, 120];
+
+pub struct Settings {
+    proxy_enabled: bool,
+    proxy_kind: ProxyKind,
+    proxy_host_state: Entity<InputState>,
+    proxy_port_state: Entity<InputState>,
+    proxy_username_state: Entity<InputState>,
+    proxy_password_state: Entity<InputState>,
+    proxy_bypass_state: Entity<InputState>,
+    mirror_state: Entity<InputState>,
+    focus_handle: FocusHandle,
+}
+
+impl Settings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let proxy = MultiHttpClient::global(cx).proxy_settings();
+        let port = if proxy.port == 0 {
+            String::new()
+        } else {
+            proxy.port.to_string()
+        };
+        let proxy_host_state = Self::input_state("地址", proxy.host, false, window, cx);
+        let proxy_port_state = Self::input_state("端口", port, false, window, cx);
+        let proxy_username_state =
+            Self::input_state("用户名 (可选)", proxy.username, false, window, cx);
+        let proxy_password_state =
+            Self::input_state("密码 (可选)", proxy.password, true, window, cx);
+        let proxy_bypass_state = Self::input_state(
+            "不使用代理的地址, 用逗号分隔, 如 localhost, *.example.com",
+            proxy.bypass.join(", "),
+            false,
+            window,
+            cx,
+        );
+        let mirror_state = Self::input_state(
+            "镜像域名, 用逗号分隔, 按顺序尝试",
+            mirror::domains().join(", "),
+            false,
+            window,
+            cx,
+        );
+
+        Self {
+            proxy_enabled: proxy.enabled,
+            proxy_kind: proxy.kind,
+            proxy_host_state,
+            proxy_port_state,
+            proxy_username_state,
+            proxy_password_state,
+            proxy_bypass_state,
+            mirror_state,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn input_state(
+        placeholder: &'static str,
+        value: String,
+        masked: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<InputState> {
+        cx.new(|cx| {
+            let mut state = InputState::new(window, cx)
+                .placeholder(placeholder)
+                .masked(masked);
+            state.set_value(value, window, cx);
+            state
+        })
+    }
+
+    fn apply_proxy(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let port = self.proxy_port_state.read(cx).value().trim().to_string();
+        let port = if port.is_empty() {
+            0
+        } else {
+            match port.parse::<u16>() {
+                Ok(port) => port,
+                Err(_) => {
+                    window.push_notification("代理端口无效", cx);
+                    return;
+                }
+            }
+        };
+        let settings = ProxySettings {
+            enabled: self.proxy_enabled,
+            kind: self.proxy_kind,
+            host: self.proxy_host_state.read(cx).value().trim().to_string(),
+            port,
+            username: self.proxy_username_state.read(cx).value().to_string(),
+            password: self.proxy_password_state.read(cx).value().to_string(),
+            bypass: self
+                .proxy_bypass_state
+                .read(cx)
+                .value()
+                .split([',', '\n'])
+                .map(|rule| rule.trim().to_string())
+                .filter(|rule| !rule.is_empty())
+                .collect(),
+        };
+
+        match MultiHttpClient::global(cx).set_proxy(settings) {
+            Ok(()) => window.push_notification("代理设置已生效", cx),
+            Err(error) => window.push_notification(error.to_string(), cx),
+        }
+    }
+
+    fn apply_mirrors(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let domains = self
+            .mirror_state
+            .read(cx)
+            .value()
+            .split([',', '\n'])
+            .map(String::from)
+            .collect();
+
+        match mirror::set_domains(domains) {
+            Ok(()) => window.push_notification("镜像设置已生效", cx),
+            Err(error) => window.push_notification(error.to_string(), cx),
+        }
+        cx.notify();
+    }
+
+    fn set_max_attempts(&mut self, max_attempts: u32, window: &mut Window, cx: &mut Context<Self>) {
+        if let Err(error) = retry::set_max_attempts(max_attempts) {
+            window.push_notification(error.to_string(), cx);
+        }
+        cx.notify();
+    }
+
+    fn set_timeout_secs(&mut self, timeout_secs: u64, window: &mut Window, cx: &mut Context<Self>) {
+        if let Err(error) = retry::set_timeout_secs(timeout_secs) {
+            window.push_notification(error.to_string(), cx);
+        }
+        cx.notify();
+    }
+
+    fn set_infinite_scroll(&mut self, infinite: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if let Err(error) = scroll::set_infinite(infinite) {
+            window.push_notification(error.to_string(), cx);
+        }
+        cx.notify();
+    }
+
+    fn set_offline(&mut self, offline: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if let Err(error) = PageCache::global(cx).set_offline(offline) {
+            window.push_notification(error.to_string(), cx);
+        }
+        cx.notify();
+    }
+
+    fn set_freshness_minutes(&mut self, minutes: u64, window: &mut Window, cx: &mut Context<Self>) {
+        if let Err(error) = PageCache::global(cx).set_freshness_minutes(minutes) {
+            window.push_notification(error.to_string(), cx);
+        }
+        cx.notify();
+    }
+
+    fn set_image_cache_mb(&mut self, max_mb: u64, window: &mut Window, cx: &mut Context<Self>) {
+        if let Err(error) = ImageCache::global(cx).set_max_mb(max_mb) {
+            window.push_notification(error.to_string(), cx);
+        }
+        cx.notify();
+    }
+
+    fn clear_image_cache(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        match ImageCache::global(cx).clear() {
+            Ok(()) => window.push_notification("已清空图片缓存", cx),
+            Err(error) => window.push_notification(error.to_string(), cx),
+        }
+        cx.notify();
+    }
+
+    fn render_section(title: impl Into<SharedString>, cx: &Context<Self>) -> Div {
+        let theme = cx.theme();
+
+        div()
+            .m_2()
+            .p_2()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .rounded_md()
+            .bg(theme.secondary_hover)
+            .border_1()
+            .border_color(theme.border)
+            .child(Label::new(title).font_semibold().text_lg())
+    }
+
+    fn render_browse(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        Self::render_section("浏览", cx).child(
+            Checkbox::new("InfiniteScroll")
+                .label("无限滚动 (滚动到底部时自动加载下一页)")
+                .checked(scroll::is_infinite())
+                .on_click(cx.listener(|this, checked: &bool, window, cx| {
+                    this.set_infinite_scroll(*checked, window, cx)
+                })),
+        )
+    }
+
+    fn render_page_cache(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let settings = PageCache::global(cx).settings();
+        let freshness = FRESHNESS_MINUTES.iter().map(|minutes| {
+            let minutes = *minutes;
+            Button::new(("CacheFreshness", minutes as usize))
+                .label(format!("{minutes} 分钟"))
+                .ghost()
+                .small()
+                .cursor_pointer()
+                .selected(settings.freshness_minutes == minutes)
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.set_freshness_minutes(minutes, window, cx)
+                }))
+        });
+
+        Self::render_section("页面缓存", cx)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(Label::new("缓存有效期").text_sm())
+                    .children(freshness),
+            )
+            .child(
+                Checkbox::new("CacheOffline")
+                    .label("离线模式 (只读取缓存)")
+                    .checked(settings.offline)
+                    .on_click(cx.listener(|this, checked: &bool, window, cx| {
+                        this.set_offline(*checked, window, cx)
+                    })),
+            )
+    }
+
+    fn render_image_cache(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let image_cache = ImageCache::global(cx);
+        let max_mb = image_cache.settings().max_mb;
+        let usage_mb = image_cache.usage() as f64 / 1024. / 1024.;
+        let limits = IMAGE_CACHE_MB.iter().map(|mb| {
+            let mb = *mb;
+            Button::new(("ImageCacheLimit", mb as usize))
+                .label(format!("{mb} MB"))
+                .ghost()
+                .small()
+                .cursor_pointer()
+                .selected(max_mb == mb)
+                .on_click(
+                    cx.listener(move |this, _, window, cx| this.set_image_cache_mb(mb, window, cx)),
+                )
+        });
+
+        Self::render_section("图片缓存", cx)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(Label::new("容量上限").text_sm())
+                    .children(limits),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(Label::new(format!("已使用 {usage_mb:.1} MB / {max_mb} MB")).text_sm())
+                    .child(
+                        Button::new("ImageCacheClear")
+                            .label("清空缓存")
+                            .small()
+                            .cursor_pointer()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.clear_image_cache(window, cx)
+                            })),
+                    ),
+            )
+    }
+
+    fn render_retry(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let settings = retry::settings();
+        let attempts = MAX_ATTEMPTS.iter().map(|attempts| {
+            let attempts = *attempts;
+            Button::new(("RetryAttempts", attempts as usize))
+                .label(format!("{attempts} 次"))
+                .ghost()
+                .small()
+                .cursor_pointer()
+                .selected(settings.max_attempts == attempts)
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.set_max_attempts(attempts, window, cx)
+                }))
+        });
+        let timeouts = TIMEOUT_SECS.iter().map(|secs| {
+            let secs = *secs;
+            Button::new(("RetryTimeout", secs as usize))
+                .label(format!("{secs} 秒"))
+                .ghost()
+                .small()
+                .cursor_pointer()
+                .selected(settings.timeout_secs == secs)
+                .on_click(
+                    cx.listener(move |this, _, window, cx| this.set_timeout_secs(secs, window, cx)),
+                )
+        });
+
+        Self::render_section("网络", cx)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(Label::new("最多尝试").text_sm())
+                    .children(attempts),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(Label::new("请求超时").text_sm())
+                    .children(timeouts),
+            )
+    }
+
+    fn render_proxy(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let kinds = ProxyKind::all()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, kind)| {
+                Button::new(("ProxyKind", idx))
+                    .label(kind.label())
+                    .ghost()
+                    .small()
+                    .cursor_pointer()
+                    .selected(self.proxy_kind == kind)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.proxy_kind = kind;
+                        cx.notify();
+                    }))
+            })
+            .collect::<Vec<_>>();
+
+        Self::render_section("代理", cx)
+            .child(
+                Checkbox::new("ProxyEnabled")
+                    .label("使用代理")
+                    .checked(self.proxy_enabled)
+                    .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                        this.proxy_enabled = *checked;
+                        cx.notify();
+                    })),
+            )
+            .child(div().flex().gap_1().children(kinds))
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(div().flex_1().child(TextInput::new(&self.proxy_host_state)))
+                    .child(div().w_24().child(TextInput::new(&self.proxy_port_state))),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(TextInput::new(&self.proxy_username_state)),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(TextInput::new(&self.proxy_password_state)),
+                    ),
+            )
+            .child(TextInput::new(&self.proxy_bypass_state))
+            .child(
+                div().flex().justify_end().child(
+                    Button::new("ProxyApply")
+                        .label("应用")
+                        .small()
+                        .cursor_pointer()
+                        .on_click(cx.listener(|this, _, window, cx| this.apply_proxy(window, cx))),
+                ),
+            )
+    }
+
+    fn render_mirror(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        Self::render_section("镜像", cx)
+            .child(TextInput::new(&self.mirror_state))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(Label::new(format!("当前使用 {}", mirror::active_host())).text_sm())
+                    .child(
+                        Button::new("MirrorApply")
+                            .label("应用")
+                            .small()
+                            .cursor_pointer()
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.apply_mirrors(window, cx)),
+                            ),
+                    ),
+            )
+    }
+}
+
+impl Render for Settings {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let browse = self.render_browse(cx);
+        let page_cache = self.render_page_cache(cx);
+        let image_cache = self.render_image_cache(cx);
+        let retry = self.render_retry(cx);
+        let proxy = self.render_proxy(cx);
+        let mirror = self.render_mirror(cx);
+
+        div()
+            .id("Settings")
+            .track_focus(&self.focus_handle)
+            .w_full()
+            .h_full()
+            .overflow_y_scroll()
+            .child(browse)
+            .child(page_cache)
+            .child(image_cache)
+            .child(retry)
+            .child(proxy)
+            .child(mirror)
+    }
+}
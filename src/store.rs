@@ -0,0 +1,63 @@
+use std::fs;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+static TMP_SEQ: AtomicU64 = AtomicU64::new(0);
+
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("bustop")
+}
+
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let path = data_dir().join(format!("{name}.json"));
+    let text = fs::read_to_string(path).ok()?;
+
+    serde_json::from_str(&text).ok()
+}
+
+pub fn save<T: Serialize>(name: &str, value: &T) -> anyhow::Result<()> {
+    write(name, value, false)
+}
+
+pub fn save_private<T: Serialize>(name: &str, value: &T) -> anyhow::Result<()> {
+    write(name, value, true)
+}
+
+fn write<T: Serialize>(name: &str, value: &T, private: bool) -> anyhow::Result<()> {
+    let dir = data_dir();
+    fs::create_dir_all(&dir).map_err(|error| anyhow::anyhow!("创建目录失败 - {error}"))?;
+    let text = serde_json::to_string_pretty(value)
+        .map_err(|error| anyhow::anyhow!("序列化失败 - {error}"))?;
+    let path = dir.join(format!("{name}.json"));
+    let tmp_path = dir.join(format!(
+        "{name}.json.{}.{}.tmp",
+        std::process::id(),
+        TMP_SEQ.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt as _;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    let result = options.open(&tmp_path).and_then(|mut file| {
+        file.write_all(text.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(error) = result.and_then(|_| fs::rename(&tmp_path, &path)) {
+        fs::remove_file(&tmp_path).ok();
+        anyhow::bail!("写入文件失败 - {error}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,302 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use gpui::{
+    App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Global, SharedString, Task,
+    WeakEntity,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Detail, Latest};
+use crate::link;
+use crate::store;
+
+const WATCH: &str = "watch";
+const POLL_TICK: Duration = Duration::from_secs(30);
+const DEFAULT_INTERVAL_MINUTES: u64 = 15;
+
+pub fn init(cx: &mut App) {
+    let watch_store = cx.new(WatchStore::new);
+    cx.set_global(GlobalWatchStore(watch_store));
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Watch {
+    pub title: SharedString,
+    pub href: SharedString,
+    pub fid: Option<u32>,
+    pub total_page: u32,
+    pub unseen_page: Option<u32>,
+    pub seen_count: u32,
+    pub latest_count: u32,
+    #[serde(default)]
+    pub first_count: u32,
+}
+
+impl Watch {
+    pub fn new(
+        title: SharedString,
+        href: SharedString,
+        fid: Option<u32>,
+        total_page: u32,
+        seen_count: u32,
+    ) -> Self {
+        Self {
+            title,
+            href,
+            fid,
+            total_page,
+            unseen_page: None,
+            seen_count,
+            latest_count: seen_count,
+            first_count: 0,
+        }
+    }
+
+    pub fn new_replies(&self) -> u32 {
+        self.latest_count.saturating_sub(self.seen_count)
+    }
+
+    fn latest(&self) -> Latest {
+        Latest {
+            total_page: self.total_page,
+            first_count: self.first_count,
+            max_count: self.latest_count,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct WatchState {
+    interval_minutes: u64,
+    threads: Vec<Watch>,
+}
+
+impl Default for WatchState {
+    fn default() -> Self {
+        Self {
+            interval_minutes: DEFAULT_INTERVAL_MINUTES,
+            threads: Vec::new(),
+        }
+    }
+}
+
+pub struct WatchStore {
+    state: WatchState,
+    _poll_task: Task<()>,
+}
+
+impl WatchStore {
+    fn new(cx: &mut Context<Self>) -> Self {
+        let mut state = store::load::<WatchState>(WATCH).unwrap_or_default();
+        if migrate(&mut state.threads) {
+            store::save(WATCH, &state).ok();
+        }
+        let poll_task = cx.spawn(async move |this, cx| Self::poll(this, cx).await);
+
+        Self {
+            state,
+            _poll_task: poll_task,
+        }
+    }
+
+    pub fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalWatchStore>().0.clone()
+    }
+
+    pub fn threads(&self) -> &[Watch] {
+        &self.state.threads
+    }
+
+    pub fn interval_minutes(&self) -> u64 {
+        self.state.interval_minutes
+    }
+
+    pub fn is_watching(&self, href: &str) -> bool {
+        let key = link::thread_key(href);
+        self.state
+            .threads
+            .iter()
+            .any(|watch| link::thread_key(&watch.href) == key)
+    }
+
+    pub fn toggle(&mut self, watch: Watch, cx: &mut Context<Self>) -> anyhow::Result<()> {
+        if self.is_watching(&watch.href) {
+            return self.remove(&watch.href, cx);
+        }
+
+        self.state.threads.push(watch);
+        cx.notify();
+
+        self.save()
+    }
+
+    pub fn remove(&mut self, href: &str, cx: &mut Context<Self>) -> anyhow::Result<()> {
+        let key = link::thread_key(href);
+        self.state
+            .threads
+            .retain(|watch| link::thread_key(&watch.href) != key);
+        cx.notify();
+
+        self.save()
+    }
+
+    pub fn set_interval_minutes(
+        &mut self,
+        interval_minutes: u64,
+        cx: &mut Context<Self>,
+    ) -> anyhow::Result<()> {
+        self.state.interval_minutes = interval_minutes.max(1);
+        cx.notify();
+
+        self.save()
+    }
+
+    pub fn mark_seen(
+        &mut self,
+        href: &str,
+        page: u32,
+        count: u32,
+        cx: &mut Context<Self>,
+    ) -> anyhow::Result<()> {
+        let key = link::thread_key(href);
+        let Some(watch) = self
+            .state
+            .threads
+            .iter_mut()
+            .find(|watch| link::thread_key(&watch.href) == key)
+        else {
+            return Ok(());
+        };
+        if count <= watch.seen_count {
+            return Ok(());
+        }
+
+        watch.seen_count = count;
+        watch.latest_count = watch.latest_count.max(count);
+        watch.total_page = watch.total_page.max(page);
+        watch.unseen_page = (watch.seen_count < watch.latest_count)
+            .then(|| watch.latest().page_of(watch.seen_count + 1));
+        cx.notify();
+
+        self.save()
+    }
+
+    fn save(&self) -> anyhow::Result<()> {
+        store::save(WATCH, &self.state)
+    }
+
+    async fn poll(this: WeakEntity<Self>, cx: &mut AsyncApp) {
+        let mut last_poll = Instant::now();
+        loop {
+            cx.background_executor().timer(POLL_TICK).await;
+
+            let Ok((interval_minutes, threads)) = this.read_with(cx, |this, _| {
+                (this.state.interval_minutes, this.state.threads.clone())
+            }) else {
+                return;
+            };
+            if last_poll.elapsed() < Duration::from_secs(interval_minutes * 60) {
+                continue;
+            }
+            last_poll = Instant::now();
+
+            let Ok(http_client) = cx.update(|cx| cx.http_client()) else {
+                return;
+            };
+            for watch in threads {
+                let latest = cx
+                    .background_spawn(Detail::load_latest(
+                        http_client.clone(),
+                        watch.href.clone(),
+                        watch.fid,
+                        watch.total_page,
+                    ))
+                    .await;
+                let Ok(latest) = latest else {
+                    continue;
+                };
+                if this
+                    .update(cx, |this, cx| this.on_latest(&watch.href, latest, cx))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn on_latest(&mut self, href: &str, latest: Latest, cx: &mut Context<Self>) {
+        let key = link::thread_key(href);
+        let Some(watch) = self
+            .state
+            .threads
+            .iter_mut()
+            .find(|watch| link::thread_key(&watch.href) == key)
+        else {
+            return;
+        };
+        if watch.seen_count == 0 {
+            watch.seen_count = latest.max_count;
+            watch.latest_count = latest.max_count;
+            watch.total_page = latest.total_page;
+            watch.first_count = latest.first_count;
+            self.save().ok();
+            return;
+        }
+        if latest.max_count <= watch.latest_count {
+            return;
+        }
+
+        let floor = watch.seen_count + 1;
+        let page = *watch.unseen_page.get_or_insert(latest.page_of(floor));
+        let event = WatchEvent::NewReplies {
+            title: watch.title.clone(),
+            href: watch.href.clone(),
+            fid: watch.fid,
+            page,
+            floor,
+            count: latest.max_count - watch.seen_count,
+        };
+        watch.latest_count = latest.max_count;
+        watch.total_page = latest.total_page;
+        watch.first_count = latest.first_count;
+        self.save().ok();
+        cx.emit(event);
+        cx.notify();
+    }
+}
+
+pub enum WatchEvent {
+    NewReplies {
+        title: SharedString,
+        href: SharedString,
+        fid: Option<u32>,
+        page: u32,
+        floor: u32,
+        count: u32,
+    },
+}
+
+impl EventEmitter<WatchEvent> for WatchStore {}
+
+fn migrate(threads: &mut Vec<Watch>) -> bool {
+    let len = threads.len();
+    let mut changed = false;
+    let mut keys = HashSet::new();
+    threads.retain_mut(|watch| {
+        let href = link::thread_href(&watch.href);
+        if href != watch.href.as_ref() {
+            watch.href = href.into();
+            changed = true;
+        }
+
+        keys.insert(link::thread_key(&watch.href))
+    });
+
+    changed || threads.len() != len
+}
+
+struct GlobalWatchStore(Entity<WatchStore>);
+
+impl Global for GlobalWatchStore {}
@@ -0,0 +1,190 @@
+use std::io::{BufRead as _, BufReader, Read as _, Write as _};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use bustop::reply;
+use chrono::NaiveDate;
+use http_client::HttpClient;
+use reqwest_client::ReqwestClient;
+
+const THREAD_PAGE: &str = r#"<html><body>
+<div id="postlist"></div>
+<form method="post" autocomplete="off" id="fastpostform" action="forum.php?mod=post&amp;action=reply&amp;fid=2&amp;tid=100&amp;extra=page%3D1&amp;replysubmit=yes&amp;infloat=yes&amp;handlekey=fastpost">
+<input type="hidden" name="formhash" value="f0rmh4sh" />
+<textarea name="message" id="fastpostmessage"></textarea>
+</form>
+</body></html>"#;
+
+const GUEST_PAGE: &str = r#"<html><body>
+<div id="postlist"></div>
+<input type="hidden" name="formhash" value="f0rmh4sh" />
+</body></html>"#;
+
+const REPLY_SUCCEED: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root><![CDATA[<script type="text/javascript">succeedhandle_fastpost('forum.php?mod=viewthread&tid=100&pid=2001&page=3&extra=page%3D1#pid2001', '非常感謝，回復發佈成功', {'fid':'2','tid':'100','pid':'2001','from':'','sechash':''});</script>]]></root>"#;
+
+const REPLY_FAILED: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root><![CDATA[<script type="text/javascript">errorhandle_fastpost('抱歉，您兩次發表間隔少於 15 秒，請稍候再發表', {});</script>]]></root>"#;
+
+struct Recorded {
+    method: String,
+    path: String,
+    body: String,
+}
+
+struct StandIn {
+    base: String,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl StandIn {
+    fn start(page: &'static str, reply: &'static str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind stand-in server");
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut parts = line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_string();
+                let path = parts.next().unwrap_or_default().to_string();
+
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim();
+                    if header.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap_or(0);
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let text = if method == "POST" { reply } else { page };
+                recorded.lock().unwrap().push(Recorded {
+                    method,
+                    path,
+                    body: String::from_utf8(body).unwrap(),
+                });
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{text}",
+                    text.len()
+                )
+                .unwrap();
+            }
+        });
+
+        Self { base, requests }
+    }
+
+    fn thread_url(&self) -> String {
+        format!("{}/forum/forum.php?mod=viewthread&tid=100", self.base)
+    }
+}
+
+fn http_client() -> Arc<dyn HttpClient> {
+    Arc::new(ReqwestClient::user_agent("bustop").expect("failed to create http client"))
+}
+
+#[test]
+fn fetch_form_resolves_action_and_formhash() {
+    let stand_in = StandIn::start(THREAD_PAGE, REPLY_SUCCEED);
+    let form = smol::block_on(reply::fetch_form(&http_client(), &stand_in.thread_url())).unwrap();
+
+    assert_eq!(
+        form.action,
+        format!(
+            "{}/forum/forum.php?mod=post&action=reply&fid=2&tid=100&extra=page%3D1&replysubmit=yes&infloat=yes&handlekey=fastpost",
+            stand_in.base
+        )
+    );
+    assert_eq!(form.formhash, "f0rmh4sh");
+}
+
+#[test]
+fn fetch_form_without_reply_form_fails() {
+    let stand_in = StandIn::start(GUEST_PAGE, REPLY_SUCCEED);
+    let result = smol::block_on(reply::fetch_form(&http_client(), &stand_in.thread_url()));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn post_sends_message_and_returns_last_page() {
+    let stand_in = StandIn::start(THREAD_PAGE, REPLY_SUCCEED);
+    let http_client = http_client();
+    let page = smol::block_on(async {
+        let form = reply::fetch_form(&http_client, &stand_in.thread_url()).await?;
+        reply::post(&http_client, &form, "感谢分享 & 支持").await
+    })
+    .unwrap();
+
+    assert_eq!(page, Some(3));
+    let requests = stand_in.requests.lock().unwrap();
+    let post = requests
+        .iter()
+        .find(|request| request.method == "POST")
+        .expect("no reply was posted");
+    assert!(
+        post.path
+            .starts_with("/forum/forum.php?mod=post&action=reply")
+    );
+    assert!(post.path.ends_with("&inajax=1"));
+    assert!(post.body.contains("formhash=f0rmh4sh"));
+    assert!(
+        post.body
+            .contains("message=%E6%84%9F%E8%B0%A2%E5%88%86%E4%BA%AB%20%26%20%E6%94%AF%E6%8C%81")
+    );
+}
+
+#[test]
+fn post_reports_server_error_message() {
+    let stand_in = StandIn::start(THREAD_PAGE, REPLY_FAILED);
+    let http_client = http_client();
+    let error = smol::block_on(async {
+        let form = reply::fetch_form(&http_client, &stand_in.thread_url()).await?;
+        reply::post(&http_client, &form, "顶").await
+    })
+    .unwrap_err();
+
+    assert!(error.to_string().contains("15 秒"));
+}
+
+#[test]
+fn post_rejects_empty_message() {
+    let form = reply::ReplyForm {
+        action: "http://127.0.0.1:9/forum/forum.php?mod=post".to_string(),
+        formhash: "f0rmh4sh".to_string(),
+    };
+    let result = smol::block_on(reply::post(&http_client(), &form, "  \n"));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn quote_formats_discuz_bbcode() {
+    let published_at = NaiveDate::from_ymd_opt(2025, 1, 2)
+        .unwrap()
+        .and_hms_opt(3, 4, 5)
+        .unwrap();
+    let quote = reply::quote("作者", published_at, "第一行\n第二行");
+
+    assert_eq!(
+        quote,
+        "[quote][size=2][color=#999999]作者 发表于 2025-01-02 03:04[/color][/size]\n第一行 第二行[/quote]\n"
+    );
+}